@@ -7,6 +7,7 @@ use crate::chapters::Chapter;
 use crate::config::AudioFormat;
 use crate::error::{Result, YtcsError};
 use crate::temp_file::TempFile;
+use crate::utils;
 use lofty::config::WriteOptions;
 use lofty::picture::{Picture, PictureType};
 use lofty::prelude::*;
@@ -160,7 +161,10 @@ pub fn split_audio_by_chapters(
         }
 
         cmd.arg("-metadata")
-            .arg(format!("title={}", chapter.title))
+            .arg(format!(
+                "title={}",
+                utils::clean_track_title(&chapter.title)
+            ))
             .arg("-metadata")
             .arg(format!("artist={}", artist))
             .arg("-metadata")
@@ -229,6 +233,335 @@ pub fn split_audio_by_chapters(
     Ok(output_files)
 }
 
+/// Escapes a value for ffmpeg's `FFMETADATA1` format (`=`, `;`, `#`, `\` and newlines
+/// must be backslash-escaped).
+fn escape_ffmetadata(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '=' | ';' | '#' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Builds an ffmpeg `FFMETADATA1` document with global `tags` and one `[CHAPTER]`
+/// section per chapter (millisecond timebase).
+///
+/// # Examples
+///
+/// ```
+/// use youtube_chapter_splitter::audio::ffmetadata_document;
+/// use youtube_chapter_splitter::Chapter;
+///
+/// let chapters = vec![Chapter::new("Intro".to_string(), 0.0, 12.5)];
+/// let doc = ffmetadata_document(&[("artist", "Marigold")], &chapters);
+/// assert!(doc.starts_with(";FFMETADATA1\nartist=Marigold\n"));
+/// assert!(doc.contains("START=0\nEND=12500\ntitle=Intro\n"));
+/// ```
+pub fn ffmetadata_document(tags: &[(&str, &str)], chapters: &[Chapter]) -> String {
+    let mut doc = String::from(";FFMETADATA1\n");
+    for (key, value) in tags {
+        doc.push_str(&format!("{}={}\n", key, escape_ffmetadata(value)));
+    }
+    for chapter in chapters {
+        doc.push_str("\n[CHAPTER]\nTIMEBASE=1/1000\n");
+        doc.push_str(&format!(
+            "START={}\nEND={}\ntitle={}\n",
+            (chapter.start_time * 1000.0).round() as u64,
+            (chapter.end_time * 1000.0).round() as u64,
+            escape_ffmetadata(&utils::clean_track_title(&chapter.title))
+        ));
+    }
+    doc
+}
+
+/// Returns the ffmpeg encoder needed to put audio from a `source_ext` file into a
+/// `target_ext` container, or `None` when the stream can be copied as-is.
+fn chaptered_encoder(source_ext: &str, target_ext: &str) -> Option<&'static str> {
+    match target_ext {
+        "m4a" | "m4b" if !matches!(source_ext, "m4a" | "m4b" | "aac") => Some("aac"),
+        "opus" if source_ext != "opus" => Some("libopus"),
+        "mp3" if source_ext != "mp3" => Some("libmp3lame"),
+        _ => None,
+    }
+}
+
+/// Writes `input_file` as a single file with `chapters` embedded as chapter markers.
+///
+/// The audio stream is copied when the output container can hold the input codec
+/// (always the case for `.mka`), and re-encoded at `audio_quality` kbps otherwise
+/// (e.g. an MP3 download written as `.m4b`). Tags are written through ffmpeg's
+/// `FFMETADATA1` format together with the chapters; the cover is attached by
+/// ffmpeg for MP3/M4A/M4B (lofty would rewrite the ID3v2 tag and drop the `CHAP`
+/// frames), as a Matroska attachment for MKA, and by lofty for Opus, whose
+/// chapters live in Vorbis comments.
+///
+/// # Arguments
+///
+/// * `input_file` - The source audio file
+/// * `chapters` - Chapter markers to embed
+/// * `output_path` - Final path of the chaptered file
+/// * `title` / `artist` / `album` - Tags for the whole file
+/// * `cover_path` - Optional path to the cover image
+/// * `audio_quality` - Bitrate in kbps, used only when the audio has to be re-encoded
+/// * `extra_date` / `extra_genre` / `extra_comment` - Optional extra tags
+/// * `overwrite_existing` - If false, fail when `output_path` already exists
+///
+/// # Errors
+///
+/// Returns an error if the target exists (and `overwrite_existing` is false),
+/// or if ffmpeg or the cover tagging fails
+#[allow(clippy::too_many_arguments)]
+pub fn write_chaptered_file(
+    input_file: &Path,
+    chapters: &[Chapter],
+    output_path: &Path,
+    title: &str,
+    artist: &str,
+    album: &str,
+    cover_path: Option<&Path>,
+    audio_quality: u32,
+    extra_date: Option<&str>,
+    extra_genre: Option<&str>,
+    extra_comment: Option<&str>,
+    overwrite_existing: bool,
+) -> Result<PathBuf> {
+    if output_path.exists() && !overwrite_existing {
+        return Err(YtcsError::AudioError(format!(
+            "File already exists (set overwrite_existing = true in config to replace): {}",
+            output_path.display()
+        )));
+    }
+
+    let output_dir = output_path.parent().unwrap_or_else(|| Path::new("."));
+    let extension = output_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let source_extension = input_file
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let mut tags = vec![
+        ("title", title),
+        ("artist", artist),
+        ("album_artist", artist),
+        ("album", album),
+    ];
+    if let Some(d) = extra_date {
+        tags.push(("date", d));
+    }
+    if let Some(g) = extra_genre {
+        tags.push(("genre", g));
+    }
+    if let Some(c) = extra_comment {
+        tags.push(("comment", c));
+    }
+
+    let metadata_file = TempFile::new(&output_dir.join(".ytcs-tmp-chapters.txt"));
+    std::fs::write(metadata_file.path(), ffmetadata_document(&tags, chapters))?;
+
+    // Same reasoning as in split_audio_by_chapters: keep the real extension so
+    // ffmpeg picks the right muxer, and only rename into place once complete.
+    let mut temp_file = TempFile::new(&output_dir.join(format!(".ytcs-tmp-full.{}", extension)));
+
+    let cover_ext = cover_path
+        .and_then(|p| p.extension())
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let cover_mime = match cover_ext.as_str() {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        _ => None,
+    };
+    let ffmpeg_cover = cover_path
+        .filter(|_| matches!(extension.as_str(), "mp3" | "m4a" | "m4b") && cover_mime.is_some());
+
+    let mut cmd = Command::new("ffmpeg");
+    cmd.arg("-i")
+        .arg(input_file)
+        .arg("-i")
+        .arg(metadata_file.path());
+    if let Some(cover) = ffmpeg_cover {
+        cmd.arg("-i").arg(cover);
+    }
+    cmd.arg("-map")
+        .arg("0:a")
+        .arg("-map_metadata")
+        .arg("1")
+        .arg("-map_chapters")
+        .arg("1");
+    match chaptered_encoder(&source_extension, &extension) {
+        Some(encoder) => {
+            cmd.arg("-c:a")
+                .arg(encoder)
+                .arg("-b:a")
+                .arg(format!("{}k", audio_quality));
+        }
+        None => {
+            cmd.arg("-c:a").arg("copy");
+        }
+    }
+    if extension == "mka"
+        && let (Some(cover), Some(mime)) = (cover_path, cover_mime)
+    {
+        cmd.arg("-attach")
+            .arg(cover)
+            .arg("-metadata:s:t")
+            .arg(format!("mimetype={}", mime))
+            .arg("-metadata:s:t")
+            .arg(format!("filename=cover.{}", cover_ext));
+    }
+    if ffmpeg_cover.is_some() {
+        cmd.arg("-map")
+            .arg("2:v")
+            .arg("-c:v")
+            .arg("copy")
+            .arg("-disposition:v:0")
+            .arg("attached_pic");
+    }
+    if extension == "mp3" {
+        cmd.arg("-id3v2_version").arg("3");
+    }
+    cmd.arg("-y").arg(temp_file.path());
+
+    let output = cmd
+        .output()
+        .map_err(|e| YtcsError::AudioError(format!("Failed to execute ffmpeg: {}", e)))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(YtcsError::AudioError(format!("ffmpeg failed: {}", error)));
+    }
+
+    if ffmpeg_cover.is_none()
+        && extension == "opus"
+        && let Some(cover) = cover_path
+        && let Some(cover_data) = load_cover_image(cover)?
+    {
+        add_cover_to_file(temp_file.path(), &cover_data)?;
+    }
+
+    std::fs::rename(temp_file.path(), output_path).map_err(|e| {
+        YtcsError::AudioError(format!(
+            "Failed to move finished file into place ({} -> {}): {}",
+            temp_file.path().display(),
+            output_path.display(),
+            e
+        ))
+    })?;
+    temp_file.keep();
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Tags a downloaded file as one track of an album and moves it to `output_path`.
+///
+/// Used when each video of a playlist is one song: the audio already has the
+/// configured format (yt-dlp extracted it), so the stream is copied as-is and
+/// only the tags (`track=n/total`) and the cover are added. As in
+/// [`split_audio_by_chapters`], the result is written to a temp file first and
+/// renamed into place once complete.
+///
+/// # Errors
+///
+/// Returns an error if the target exists (and `overwrite_existing` is false),
+/// or if ffmpeg or the cover tagging fails
+#[allow(clippy::too_many_arguments)]
+pub fn write_album_track(
+    input_file: &Path,
+    output_path: &Path,
+    track_number: usize,
+    total_tracks: usize,
+    title: &str,
+    artist: &str,
+    album: &str,
+    cover_path: Option<&Path>,
+    extra_date: Option<&str>,
+    extra_genre: Option<&str>,
+    extra_comment: Option<&str>,
+    overwrite_existing: bool,
+) -> Result<PathBuf> {
+    if output_path.exists() && !overwrite_existing {
+        return Err(YtcsError::AudioError(format!(
+            "File already exists (set overwrite_existing = true in config to replace): {}",
+            output_path.display()
+        )));
+    }
+
+    let output_dir = output_path.parent().unwrap_or_else(|| Path::new("."));
+    let extension = output_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    let mut temp_file =
+        TempFile::new(&output_dir.join(format!(".ytcs-tmp-{:03}.{}", track_number, extension)));
+
+    let mut cmd = Command::new("ffmpeg");
+    cmd.arg("-i")
+        .arg(input_file)
+        .arg("-map")
+        .arg("0:a")
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-metadata")
+        .arg(format!("title={}", utils::clean_track_title(title)))
+        .arg("-metadata")
+        .arg(format!("artist={}", artist))
+        .arg("-metadata")
+        .arg(format!("album={}", album))
+        .arg("-metadata")
+        .arg(format!("track={}/{}", track_number, total_tracks));
+    if let Some(d) = extra_date {
+        cmd.arg("-metadata").arg(format!("date={}", d));
+    }
+    if let Some(g) = extra_genre {
+        cmd.arg("-metadata").arg(format!("genre={}", g));
+    }
+    if let Some(c) = extra_comment {
+        cmd.arg("-metadata").arg(format!("comment={}", c));
+    }
+    cmd.arg("-y").arg(temp_file.path());
+
+    let output = cmd
+        .output()
+        .map_err(|e| YtcsError::AudioError(format!("Failed to execute ffmpeg: {}", e)))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(YtcsError::AudioError(format!("ffmpeg failed: {}", error)));
+    }
+
+    if let Some(cover) = cover_path
+        && let Some(cover_data) = load_cover_image(cover)?
+    {
+        add_cover_to_file(temp_file.path(), &cover_data)?;
+    }
+
+    std::fs::rename(temp_file.path(), output_path).map_err(|e| {
+        YtcsError::AudioError(format!(
+            "Failed to move finished track into place ({} -> {}): {}",
+            temp_file.path().display(),
+            output_path.display(),
+            e
+        ))
+    })?;
+    temp_file.keep();
+
+    Ok(output_path.to_path_buf())
+}
+
 /// Writes a simple M3U playlist listing `track_paths` with paths relative to `output_dir`.
 pub fn write_m3u_playlist(output_dir: &Path, track_paths: &[PathBuf]) -> Result<PathBuf> {
     let m3u_path = output_dir.join("playlist.m3u");
@@ -309,6 +642,43 @@ fn add_cover_to_file(audio_path: &Path, cover_data: &[u8]) -> Result<()> {
     Ok(())
 }
 
+/// Writes `input_file` without the given time ranges to `output_file`.
+///
+/// Used to cut SponsorBlock segments out before splitting. The container is
+/// chosen from `output_file`'s extension (a lossless one such as `.flac`
+/// avoids a second lossy generation, since every track is re-encoded anyway).
+///
+/// # Errors
+///
+/// Returns an `AudioError` if ffmpeg can't be run or fails
+pub fn remove_time_ranges(
+    input_file: &Path,
+    ranges: &[(f64, f64)],
+    output_file: &Path,
+) -> Result<()> {
+    let selection = ranges
+        .iter()
+        .map(|(start, end)| format!("between(t,{:.3},{:.3})", start, end))
+        .collect::<Vec<_>>()
+        .join("+");
+    let output = Command::new("ffmpeg")
+        .arg("-i")
+        .arg(input_file)
+        .arg("-vn")
+        .arg("-af")
+        .arg(format!("aselect='not({})',asetpts=N/SR/TB", selection))
+        .arg("-y")
+        .arg(output_file)
+        .output()
+        .map_err(|e| YtcsError::AudioError(format!("Failed to execute ffmpeg: {}", e)))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(YtcsError::AudioError(format!("ffmpeg failed: {}", error)));
+    }
+    Ok(())
+}
+
 /// Automatically detects chapters by analyzing silence periods.
 ///
 /// Uses `ffmpeg` with the `silencedetect` filter to identify potential
@@ -336,6 +706,34 @@ pub fn detect_silence_chapters(
         println!("Detecting silence to identify tracks...");
     }
 
+    let silence_periods: Vec<f64> =
+        detect_silences(input_file, silence_threshold, min_silence_duration)?
+            .iter()
+            .map(|(start, end)| (start + end) / 2.0)
+            .collect();
+
+    if silence_periods.is_empty() {
+        return Err(YtcsError::ChapterError(
+            "No silence detected. Try adjusting the parameters.".to_string(),
+        ));
+    }
+
+    // Get total duration
+    let duration = get_audio_duration(input_file)?;
+    let chapters = chapters_from_cuts(&silence_periods, duration)?;
+
+    if !crate::ui::is_output_quiet() {
+        println!("✓ {} tracks detected", chapters.len());
+    }
+    Ok(chapters)
+}
+
+/// Runs ffmpeg's `silencedetect` and returns the `(start, end)` of every silence.
+fn detect_silences(
+    input_file: &Path,
+    silence_threshold: f64,
+    min_silence_duration: f64,
+) -> Result<Vec<(f64, f64)>> {
     let output = Command::new("ffmpeg")
         .arg("-i")
         .arg(input_file)
@@ -357,7 +755,7 @@ pub fn detect_silence_chapters(
 
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    let mut silence_periods = Vec::new();
+    let mut silences = Vec::new();
     let mut current_start: Option<f64> = None;
 
     for line in stderr.lines() {
@@ -369,40 +767,167 @@ pub fn detect_silence_chapters(
             && let (Some(start), Some(end_str)) = (current_start, caps.get(1))
         {
             if let Ok(end) = end_str.as_str().parse::<f64>() {
-                let mid_point = (start + end) / 2.0;
-                silence_periods.push(mid_point);
+                silences.push((start, end));
             }
             current_start = None;
         }
     }
+    Ok(silences)
+}
 
-    if silence_periods.is_empty() {
-        return Err(YtcsError::ChapterError(
-            "No silence detected. Try adjusting the parameters.".to_string(),
-        ));
-    }
-
-    // Get total duration
-    let duration = get_audio_duration(input_file)?;
-
+/// `Track 1`, `Track 2`, … chapters between consecutive cut points.
+fn chapters_from_cuts(cuts: &[f64], duration: f64) -> Result<Vec<Chapter>> {
     let mut chapters = Vec::new();
     let mut start_time = 0.0;
 
-    for (i, &split_point) in silence_periods.iter().enumerate() {
-        chapters.push(Chapter::new(
+    for (i, &split_point) in cuts.iter().enumerate() {
+        chapters.push(Chapter::try_new(
             format!("Track {}", i + 1),
             start_time,
             split_point,
-        ));
+        )?);
         start_time = split_point;
     }
 
     // Last track
-    chapters.push(Chapter::new(
+    chapters.push(Chapter::try_new(
         format!("Track {}", chapters.len() + 1),
         start_time,
         duration,
-    ));
+    )?);
+    Ok(chapters)
+}
+
+/// Silence settings (noise dB, minimum seconds) tried by
+/// [`detect_silence_chapters_for_count`], strictest first.
+const TRACK_COUNT_LEVELS: [(f64, f64); 5] = [
+    (-50.0, 1.5),
+    (-40.0, 1.0),
+    (-35.0, 0.8),
+    (-30.0, 0.5),
+    (-25.0, 0.3),
+];
+
+/// A silence found while searching for a given number of tracks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SilenceCandidate {
+    pub start: f64,
+    pub end: f64,
+    /// Index of the strictest setting that found it (0 = quietest and longest)
+    pub level: usize,
+}
+
+impl SilenceCandidate {
+    fn midpoint(&self) -> f64 {
+        (self.start + self.end) / 2.0
+    }
+}
+
+/// Chooses the `tracks - 1` most prominent silences as cut points.
+///
+/// Silences found at a stricter setting come first, longer ones before
+/// shorter ones; a silence is skipped when cutting there would leave a track
+/// shorter than `min_track` seconds. Returns the cuts in playback order, or
+/// `None` when there aren't enough usable silences.
+pub fn pick_track_cuts(
+    candidates: &[SilenceCandidate],
+    duration: f64,
+    tracks: usize,
+    min_track: f64,
+) -> Option<Vec<f64>> {
+    let wanted = tracks.checked_sub(1)?;
+    let mut ranked: Vec<&SilenceCandidate> = candidates.iter().collect();
+    ranked.sort_by(|a, b| {
+        a.level
+            .cmp(&b.level)
+            .then((b.end - b.start).total_cmp(&(a.end - a.start)))
+    });
+
+    let mut cuts: Vec<f64> = Vec::new();
+    for candidate in ranked {
+        if cuts.len() == wanted {
+            break;
+        }
+        let cut = candidate.midpoint();
+        if cut >= min_track
+            && duration - cut >= min_track
+            && cuts.iter().all(|c| (c - cut).abs() >= min_track)
+        {
+            cuts.push(cut);
+        }
+    }
+    if cuts.len() < wanted {
+        return None;
+    }
+    cuts.sort_by(f64::total_cmp);
+    Some(cuts)
+}
+
+/// Detects exactly `tracks` tracks by silence.
+///
+/// Starts with a strict setting (very quiet, long silences) and relaxes the
+/// noise threshold and minimum duration step by step until `tracks - 1`
+/// usable silences are found, then cuts at the most prominent ones (see
+/// [`pick_track_cuts`]).
+///
+/// # Errors
+///
+/// Returns a `ChapterError` if even the most lenient setting finds too few
+/// silences, or an error if ffmpeg fails
+pub fn detect_silence_chapters_for_count(
+    input_file: &Path,
+    tracks: usize,
+    min_track: f64,
+) -> Result<Vec<Chapter>> {
+    if tracks == 0 {
+        return Err(YtcsError::ChapterError(
+            "The track count must be at least 1".to_string(),
+        ));
+    }
+    if !crate::ui::is_output_quiet() {
+        println!("Detecting silence to find {} tracks...", tracks);
+    }
+    let duration = get_audio_duration(input_file)?;
+    if tracks == 1 {
+        return chapters_from_cuts(&[], duration);
+    }
+
+    let mut candidates: Vec<SilenceCandidate> = Vec::new();
+    for (level, (noise, min_silence)) in TRACK_COUNT_LEVELS.iter().enumerate() {
+        for (start, end) in detect_silences(input_file, *noise, *min_silence)? {
+            // The same silence seen at a stricter setting keeps its rank
+            match candidates
+                .iter_mut()
+                .find(|c| start < c.end && c.start < end)
+            {
+                Some(known) => {
+                    known.start = known.start.min(start);
+                    known.end = known.end.max(end);
+                }
+                None => candidates.push(SilenceCandidate { start, end, level }),
+            }
+        }
+        log::debug!(
+            "{} silences at {} dB / {}s",
+            candidates.len(),
+            noise,
+            min_silence
+        );
+        if pick_track_cuts(&candidates, duration, tracks, min_track).is_some() {
+            break;
+        }
+    }
+
+    let cuts = pick_track_cuts(&candidates, duration, tracks, min_track).ok_or_else(|| {
+        YtcsError::ChapterError(format!(
+            "Could not find {} silences at least {}s apart for {} tracks ({} silences found)",
+            tracks - 1,
+            min_track,
+            tracks,
+            candidates.len()
+        ))
+    })?;
+    let chapters = chapters_from_cuts(&cuts, duration)?;
 
     if !crate::ui::is_output_quiet() {
         println!("✓ {} tracks detected", chapters.len());
@@ -410,6 +935,44 @@ pub fn detect_silence_chapters(
     Ok(chapters)
 }
 
+/// Merges tracks shorter than `min_track` seconds into a neighbour.
+///
+/// The shortest track goes first, into whichever neighbour is shorter, until
+/// every track is long enough (or one is left). Meant for silence-detected
+/// tracks: titles are renumbered `Track 1`, `Track 2`, …
+pub fn merge_short_tracks(chapters: &[Chapter], min_track: f64) -> Vec<Chapter> {
+    let mut merged = chapters.to_vec();
+    while merged.len() > 1 {
+        let Some(short) = (0..merged.len())
+            .filter(|&i| merged[i].duration() < min_track)
+            .min_by(|&a, &b| merged[a].duration().total_cmp(&merged[b].duration()))
+        else {
+            break;
+        };
+        let neighbour = match (short.checked_sub(1), merged.get(short + 1)) {
+            (Some(previous), Some(next)) if merged[previous].duration() > next.duration() => {
+                short + 1
+            }
+            (Some(previous), _) => previous,
+            (None, _) => short + 1,
+        };
+        let (first, second) = (short.min(neighbour), short.max(neighbour));
+        merged[first].end_time = merged[second].end_time;
+        merged.remove(second);
+    }
+    if merged.len() < chapters.len() {
+        log::info!(
+            "Merged {} tracks shorter than {}s",
+            chapters.len() - merged.len(),
+            min_track
+        );
+    }
+    for (i, chapter) in merged.iter_mut().enumerate() {
+        chapter.title = format!("Track {}", i + 1);
+    }
+    merged
+}
+
 /// Gets the total duration of an audio file.
 ///
 /// Uses `ffprobe` to extract the file duration.
@@ -0,0 +1,687 @@
+//! Bounded worker pool and multi-line progress display for playlist batches.
+//!
+//! Playlist entries spend most of their time waiting on the network, so
+//! several of them are downloaded at once while the CPU-heavy ffmpeg work
+//! (silence detection, splitting) is throttled by a separate [`Semaphore`].
+//! Every entry's outcome is collected into a [`BatchReport`] so one failure
+//! doesn't stop the rest.
+//!
+//! Batches come from a playlist URL or from a URL list (`--batch-file`),
+//! parsed by [`parse_batch_list`].
+
+use crate::error::{Result, YtcsError};
+use crate::ytdlp_error_parser;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// One video (or playlist URL, before expansion) to process, with the
+/// overrides that apply to it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VideoJob {
+    pub url: String,
+    /// Forced artist (like `--artist`)
+    pub artist: Option<String>,
+    /// Forced album (like `--album`)
+    pub album: Option<String>,
+    /// Text file with a timestamped tracklist to use instead of the video's chapters
+    pub chapters: Option<PathBuf>,
+    /// `url` is a playlist processed as one album, each video becoming a track
+    pub as_album: bool,
+    /// Name of the channel the playlist was listed on (channel playlists
+    /// tab), used as the artist unless one is forced. Not part of the list line.
+    pub channel_artist: Option<String>,
+}
+
+impl VideoJob {
+    /// A job without overrides.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The same overrides applied to another URL (a playlist entry of this line).
+    pub fn with_url(&self, url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// The job as a batch-list line, the inverse of [`parse_batch_list`].
+    pub fn to_list_line(&self) -> String {
+        let mut line = self.url.clone();
+        let chapters = self.chapters.as_ref().map(|p| p.display().to_string());
+        for (key, value) in [
+            ("artist", self.artist.as_ref()),
+            ("album", self.album.as_ref()),
+            ("chapters", chapters.as_ref()),
+        ] {
+            if let Some(value) = value {
+                if value.contains(char::is_whitespace) || value.contains(['"', '\\']) {
+                    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+                    let _ = write!(line, " {}=\"{}\"", key, escaped);
+                } else {
+                    let _ = write!(line, " {}={}", key, value);
+                }
+            }
+        }
+        if self.as_album {
+            line.push_str(" mode=album");
+        }
+        line
+    }
+}
+
+/// Splits a list line into whitespace-separated fields; double quotes group
+/// a value containing spaces (`album="Live at Pompeii"`). Inside quotes `\"`
+/// is a literal quote and `\\` a literal backslash; any other backslash is
+/// kept as is, so Windows paths need no escaping.
+fn split_list_fields(line: &str) -> std::result::Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_quotes => match chars.next_if(|next| matches!(next, '"' | '\\')) {
+                Some(escaped) => current.push(escaped),
+                None => current.push(c),
+            },
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    fields.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("unterminated quote".to_string());
+    }
+    if !current.is_empty() {
+        fields.push(current);
+    }
+    Ok(fields)
+}
+
+/// Parses a URL list: one URL per line, optionally followed by `key=value`
+/// overrides (`artist=`, `album=`, `chapters=`, and `mode=album` to treat a
+/// playlist URL as one album). Blank lines and lines starting
+/// with `#` are ignored, so the failures list of a previous batch can be fed
+/// back in as-is.
+///
+/// # Errors
+///
+/// Returns an error naming the line for an unknown key, a field without `=`,
+/// or an unterminated quote
+///
+/// # Examples
+///
+/// ```
+/// use youtube_chapter_splitter::batch::parse_batch_list;
+///
+/// let jobs = parse_batch_list(
+///     "# weekend queue\nhttps://youtu.be/abc album=\"Live at Pompeii\"\n\nhttps://youtu.be/def\n",
+/// )
+/// .unwrap();
+/// assert_eq!(jobs.len(), 2);
+/// assert_eq!(jobs[0].album.as_deref(), Some("Live at Pompeii"));
+/// assert_eq!(jobs[1].url, "https://youtu.be/def");
+/// ```
+pub fn parse_batch_list(text: &str) -> Result<Vec<VideoJob>> {
+    let mut jobs = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |reason: String| {
+            YtcsError::ConfigError(format!("batch list line {}: {}", index + 1, reason))
+        };
+
+        let fields = split_list_fields(line).map_err(invalid)?;
+        let mut fields = fields.into_iter();
+        let mut job = VideoJob::new(&fields.next().unwrap_or_default());
+        for field in fields {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("expected key=value, got {:?}", field)))?;
+            let value = value.trim().to_string();
+            match key.trim() {
+                "artist" => job.artist = Some(value),
+                "album" => job.album = Some(value),
+                "chapters" => {
+                    job.chapters = Some(PathBuf::from(shellexpand::tilde(&value).as_ref()))
+                }
+                "mode" if value == "album" => job.as_album = true,
+                "mode" => {
+                    return Err(invalid(format!(
+                        "unknown mode {:?} (expected album)",
+                        value
+                    )));
+                }
+                other => {
+                    return Err(invalid(format!(
+                        "unknown key {:?} (expected artist, album, chapters or mode)",
+                        other
+                    )));
+                }
+            }
+        }
+        jobs.push(job);
+    }
+    Ok(jobs)
+}
+
+/// Result of processing one video successfully.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VideoOutcome {
+    /// Output written to this album folder
+    Done(PathBuf),
+    /// Nothing to do (e.g. every track already exists), with the reason
+    Skipped(String),
+}
+
+/// Final state of one batch entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryStatus {
+    Done(PathBuf),
+    Skipped(String),
+    Failed {
+        /// Short category, e.g. `rate limited` or `audio`
+        reason: String,
+        message: String,
+    },
+}
+
+/// One row of the batch summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchEntry {
+    /// The entry as a batch-list line (URL plus any overrides)
+    pub url: String,
+    pub status: EntryStatus,
+}
+
+/// Outcomes of every entry of a batch, in input order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchReport {
+    pub entries: Vec<BatchEntry>,
+}
+
+/// Short category of a failure for the summary table; yt-dlp errors are
+/// grouped with [`ytdlp_error_parser::categorize_ytdlp_error`].
+pub fn failure_reason(error: &YtcsError) -> String {
+    match error {
+        YtcsError::DownloadError(msg) => ytdlp_error_parser::categorize_ytdlp_error(msg)
+            .label()
+            .to_string(),
+        YtcsError::ThumbnailFailed { .. } => "thumbnail".to_string(),
+        YtcsError::AudioError(_) => "audio".to_string(),
+        YtcsError::ChapterError(_) => "chapters".to_string(),
+        YtcsError::InvalidUrl(_) => "invalid URL".to_string(),
+        YtcsError::InputRequired(_) => "input required".to_string(),
+        YtcsError::IoError(_) => "i/o".to_string(),
+        _ => "other".to_string(),
+    }
+}
+
+impl BatchReport {
+    /// Records the result of processing `url` (a batch-list line).
+    pub fn push(&mut self, url: &str, result: Result<VideoOutcome>) {
+        let status = match result {
+            Ok(VideoOutcome::Done(dir)) => EntryStatus::Done(dir),
+            Ok(VideoOutcome::Skipped(reason)) => EntryStatus::Skipped(reason),
+            Err(e) => EntryStatus::Failed {
+                reason: failure_reason(&e),
+                message: e.to_string(),
+            },
+        };
+        self.entries.push(BatchEntry {
+            url: url.to_string(),
+            status,
+        });
+    }
+
+    /// Number of entries that were processed successfully.
+    pub fn done_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, EntryStatus::Done(_)))
+            .count()
+    }
+
+    /// Number of entries that were skipped.
+    pub fn skipped_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, EntryStatus::Skipped(_)))
+            .count()
+    }
+
+    /// Number of entries that failed.
+    pub fn failed_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, EntryStatus::Failed { .. }))
+            .count()
+    }
+
+    /// Failed URLs, one per line, each preceded by a `#` comment with the
+    /// reason, so the file can be fed back in as a URL list.
+    pub fn failures_list(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            if let EntryStatus::Failed { reason, message } = &entry.status {
+                let first_line = message.lines().next().unwrap_or("");
+                let _ = writeln!(out, "# {}: {}", reason, first_line);
+                let _ = writeln!(out, "{}", entry.url);
+            }
+        }
+        out
+    }
+
+    /// Writes [`Self::failures_list`] to `path`.
+    pub fn write_failures_list(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.failures_list())?;
+        Ok(())
+    }
+
+    /// `Ok` when nothing failed, otherwise [`YtcsError::BatchIncomplete`].
+    pub fn into_result(self) -> Result<()> {
+        match self.failed_count() {
+            0 => Ok(()),
+            failed => Err(YtcsError::BatchIncomplete {
+                failed,
+                total: self.entries.len(),
+            }),
+        }
+    }
+}
+
+/// Counting semaphore used to cap how many videos are split at the same time.
+pub struct Semaphore {
+    available: Mutex<usize>,
+    released: Condvar,
+}
+
+/// Slot held from [`Semaphore::acquire`] until dropped.
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Semaphore {
+    /// Creates a semaphore with `permits` slots (at least one).
+    pub fn new(permits: usize) -> Self {
+        Self {
+            available: Mutex::new(permits.max(1)),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Blocks until a slot is free and returns it.
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        let mut available = self.available.lock().unwrap_or_else(|e| e.into_inner());
+        while *available == 0 {
+            available = self
+                .released
+                .wait(available)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        *available -= 1;
+        SemaphorePermit { semaphore: self }
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        let mut available = self
+            .semaphore
+            .available
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        *available += 1;
+        self.semaphore.released.notify_one();
+    }
+}
+
+/// Output folders in use by a worker. Entries that land in the same folder
+/// share its scratch files (`temp_audio.*`, `.ytcs-tmp-*`), so they run one
+/// after the other.
+#[derive(Default)]
+pub struct DirLocks {
+    busy: Mutex<HashSet<PathBuf>>,
+    released: Condvar,
+}
+
+/// Folder held from [`DirLocks::lock`] until dropped.
+pub struct DirLock<'a> {
+    locks: &'a DirLocks,
+    dir: PathBuf,
+}
+
+impl DirLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Blocks until no other worker holds `dir` and takes it.
+    pub fn lock(&self, dir: &Path) -> DirLock<'_> {
+        let mut busy = self.busy.lock().unwrap_or_else(|e| e.into_inner());
+        while busy.contains(dir) {
+            busy = self.released.wait(busy).unwrap_or_else(|e| e.into_inner());
+        }
+        busy.insert(dir.to_path_buf());
+        DirLock {
+            locks: self,
+            dir: dir.to_path_buf(),
+        }
+    }
+}
+
+impl Drop for DirLock<'_> {
+    fn drop(&mut self) {
+        let mut busy = self.locks.busy.lock().unwrap_or_else(|e| e.into_inner());
+        busy.remove(&self.dir);
+        self.locks.released.notify_all();
+    }
+}
+
+/// Runs `job(0..total)` on at most `workers` threads and returns the results in
+/// index order.
+///
+/// Each job runs to completion independently: a job that returns an error (or
+/// panics, which is reported as `None`) doesn't stop the others.
+///
+/// # Examples
+///
+/// ```
+/// use youtube_chapter_splitter::batch::run_pool;
+///
+/// let squares = run_pool(5, 3, |i| i * i);
+/// assert_eq!(squares, vec![Some(0), Some(1), Some(4), Some(9), Some(16)]);
+/// ```
+pub fn run_pool<R, F>(total: usize, workers: usize, job: F) -> Vec<Option<R>>
+where
+    R: Send,
+    F: Fn(usize) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..total).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, total.max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    if index >= total {
+                        break;
+                    }
+                    let outcome =
+                        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| job(index)));
+                    if let Ok(value) = outcome {
+                        results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(value);
+                    }
+                }
+            });
+        }
+    });
+
+    results.into_inner().unwrap_or_else(|e| e.into_inner())
+}
+
+/// One status line per video (plus a transient download bar) on a shared
+/// [`MultiProgress`].
+pub struct BatchProgress {
+    multi: MultiProgress,
+    prompt_lock: Mutex<()>,
+}
+
+impl BatchProgress {
+    /// Creates the display; `hidden` draws nothing (used with `--quiet`).
+    pub fn new(hidden: bool) -> Self {
+        let multi = MultiProgress::new();
+        if hidden {
+            multi.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        Self {
+            multi,
+            prompt_lock: Mutex::new(()),
+        }
+    }
+
+    /// Adds the status line for one video, e.g. `[03/12] queued`.
+    pub fn video_line(&self, index: usize, total: usize) -> ProgressBar {
+        let width = total.to_string().len();
+        let pb = self.multi.add(ProgressBar::new_spinner());
+        pb.set_style(
+            ProgressStyle::default_spinner()
+                .template("{prefix} {spinner:.green} {msg}")
+                .unwrap(),
+        );
+        pb.set_prefix(format!("[{:0width$}/{}]", index + 1, total, width = width));
+        pb.set_message("queued");
+        pb
+    }
+
+    /// Adds a download bar right below `line`; it clears itself when the
+    /// download finishes.
+    pub fn download_bar(&self, line: &ProgressBar) -> ProgressBar {
+        let pb = self.multi.insert_after(line, ProgressBar::new(100));
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("      [{bar:40}] {msg}")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+        pb.set_message("Downloading audio...");
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb
+    }
+
+    /// Runs `f` (typically a stdin prompt) with the progress display paused and
+    /// no other worker prompting at the same time.
+    pub fn suspend<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.prompt_lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.multi.suspend(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_run_pool_keeps_index_order() {
+        let results = run_pool(20, 4, |i| {
+            std::thread::sleep(Duration::from_millis((20 - i as u64) % 5));
+            i * 2
+        });
+        let expected: Vec<Option<usize>> = (0..20).map(|i| Some(i * 2)).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_run_pool_isolates_failures() {
+        let results = run_pool(4, 2, |i| if i == 1 { Err("boom") } else { Ok(i) });
+        assert_eq!(results[0], Some(Ok(0)));
+        assert_eq!(results[1], Some(Err("boom")));
+        assert_eq!(results[3], Some(Ok(3)));
+    }
+
+    #[test]
+    fn test_run_pool_empty() {
+        let results: Vec<Option<()>> = run_pool(0, 4, |_| ());
+        assert!(results.is_empty());
+    }
+
+    fn sample_report() -> BatchReport {
+        let mut report = BatchReport::default();
+        report.push(
+            "https://www.youtube.com/watch?v=aaaaaaaaaaa",
+            Ok(VideoOutcome::Done(PathBuf::from("/music/A - B"))),
+        );
+        report.push(
+            "https://www.youtube.com/watch?v=bbbbbbbbbbb",
+            Err(YtcsError::DownloadError(
+                "This video is no longer available (deleted or made private)".to_string(),
+            )),
+        );
+        report.push(
+            "https://www.youtube.com/watch?v=ccccccccccc",
+            Ok(VideoOutcome::Skipped(
+                "all tracks already exist".to_string(),
+            )),
+        );
+        report
+    }
+
+    #[test]
+    fn test_batch_report_counts_and_reason() {
+        let report = sample_report();
+        assert_eq!(report.done_count(), 1);
+        assert_eq!(report.failed_count(), 1);
+        assert_eq!(report.skipped_count(), 1);
+        assert!(matches!(
+            &report.entries[1].status,
+            EntryStatus::Failed { reason, .. } if reason == "unavailable"
+        ));
+    }
+
+    #[test]
+    fn test_failures_list_contains_only_failed_urls() {
+        let list = sample_report().failures_list();
+        let urls: Vec<&str> = list.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(urls, vec!["https://www.youtube.com/watch?v=bbbbbbbbbbb"]);
+        assert!(list.starts_with("# unavailable: "));
+    }
+
+    #[test]
+    fn test_into_result_reports_partial_failure() {
+        match sample_report().into_result() {
+            Err(
+                e @ YtcsError::BatchIncomplete {
+                    failed: 1,
+                    total: 3,
+                },
+            ) => {
+                assert_eq!(e.exit_code(), 3)
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+        assert!(BatchReport::default().into_result().is_ok());
+    }
+
+    #[test]
+    fn test_parse_batch_list_overrides_and_comments() {
+        let text = "\
+# retry list
+   https://www.youtube.com/watch?v=aaaaaaaaaaa   artist=Marigold album=\"Oblivion Gate\"
+https://www.youtube.com/watch?v=bbbbbbbbbbb chapters=/tmp/tracks.txt
+";
+        let jobs = parse_batch_list(text).unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].url, "https://www.youtube.com/watch?v=aaaaaaaaaaa");
+        assert_eq!(jobs[0].artist.as_deref(), Some("Marigold"));
+        assert_eq!(jobs[0].album.as_deref(), Some("Oblivion Gate"));
+        assert_eq!(jobs[1].chapters, Some(PathBuf::from("/tmp/tracks.txt")));
+        assert_eq!(jobs[1].artist, None);
+    }
+
+    #[test]
+    fn test_parse_batch_list_rejects_bad_fields() {
+        let err = parse_batch_list("https://youtu.be/x\nhttps://youtu.be/y genre=rock\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 2"), "{}", err);
+        assert!(parse_batch_list("https://youtu.be/x album").is_err());
+        assert!(parse_batch_list("https://youtu.be/x album=\"open").is_err());
+    }
+
+    #[test]
+    fn test_list_line_roundtrip() {
+        let job = VideoJob {
+            url: "https://youtu.be/x".to_string(),
+            artist: Some("The \"Band\"".to_string()),
+            album: Some("Live at Pompeii".to_string()),
+            chapters: None,
+            as_album: true,
+            channel_artist: None,
+        };
+        let parsed = parse_batch_list(&job.to_list_line()).unwrap();
+        assert_eq!(parsed, vec![job]);
+    }
+
+    #[test]
+    fn test_list_line_keeps_windows_paths() {
+        let jobs =
+            parse_batch_list("https://youtu.be/x chapters=\"C:\\My Music\\list.txt\"").unwrap();
+        assert_eq!(
+            jobs[0].chapters,
+            Some(PathBuf::from("C:\\My Music\\list.txt"))
+        );
+
+        let job = VideoJob {
+            url: "https://youtu.be/x".to_string(),
+            album: Some("Back\\Slash \"Live\"".to_string()),
+            chapters: Some(PathBuf::from("C:\\My Music\\list.txt")),
+            ..Default::default()
+        };
+        let line = job.to_list_line();
+        assert!(
+            line.contains("chapters=\"C:\\\\My Music\\\\list.txt\""),
+            "{}",
+            line
+        );
+        assert_eq!(parse_batch_list(&line).unwrap(), vec![job]);
+    }
+
+    #[test]
+    fn test_semaphore_caps_concurrency() {
+        let semaphore = Semaphore::new(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        run_pool(8, 8, |_| {
+            let _permit = semaphore.acquire();
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(10));
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn test_dir_locks_serialize_same_folder() {
+        let locks = DirLocks::new();
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        run_pool(6, 6, |i| {
+            let dir = if i % 2 == 0 {
+                "Artist - Album"
+            } else {
+                "Other"
+            };
+            let _lock = locks.lock(Path::new(dir));
+            if i % 2 == 0 {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(10));
+                running.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,101 @@
+//! Leaving chapters out by title.
+//!
+//! A [`ChapterFilter`] keeps the chapters whose title matches `include` (when
+//! set) and doesn't match `exclude` (when set). The other chapters are either
+//! dropped with their audio, or merged into a neighbouring track (see
+//! [`ExcludedChapters`]). Track numbers follow the returned order, so they are
+//! recomputed automatically when splitting.
+
+use crate::chapters::Chapter;
+use crate::config::ExcludedChapters;
+use crate::error::{Result, YtcsError};
+use crate::playlist_selection::title_pattern;
+use regex::Regex;
+
+/// Which chapters become tracks.
+#[derive(Debug, Clone, Default)]
+pub struct ChapterFilter {
+    /// Keep only chapters whose title matches this pattern
+    pub include: Option<Regex>,
+
+    /// Leave out chapters whose title matches this pattern
+    pub exclude: Option<Regex>,
+
+    /// What happens to the audio of a left-out chapter
+    pub excluded: ExcludedChapters,
+}
+
+impl ChapterFilter {
+    /// Builds a filter from (case-insensitive) pattern strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if a pattern is not a valid regex
+    pub fn new(
+        include: Option<&str>,
+        exclude: Option<&str>,
+        excluded: ExcludedChapters,
+    ) -> Result<Self> {
+        Ok(Self {
+            include: include.map(title_pattern).transpose()?,
+            exclude: exclude.map(title_pattern).transpose()?,
+            excluded,
+        })
+    }
+
+    /// Returns true if every chapter would be kept unchanged.
+    pub fn is_all(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none()
+    }
+
+    /// Returns true if a chapter with this title becomes a track.
+    pub fn keeps(&self, title: &str) -> bool {
+        self.include.as_ref().is_none_or(|re| re.is_match(title))
+            && !self.exclude.as_ref().is_some_and(|re| re.is_match(title))
+    }
+
+    /// Applies the filter to `chapters` (in playback order).
+    ///
+    /// With [`ExcludedChapters::Merge`] a left-out chapter extends the kept
+    /// chapter before it, or the one after it when nothing is kept before
+    /// (a leading intro joins the first track).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChapterError` if no chapter is left
+    pub fn apply(&self, chapters: &[Chapter]) -> Result<Vec<Chapter>> {
+        if self.is_all() {
+            return Ok(chapters.to_vec());
+        }
+
+        let mut kept: Vec<Chapter> = Vec::new();
+        // Start of left-out chapters waiting for the next kept one
+        let mut pending_start: Option<f64> = None;
+        for chapter in chapters {
+            if self.keeps(&chapter.title) {
+                let mut chapter = chapter.clone();
+                if let Some(start) = pending_start.take() {
+                    chapter.start_time = start;
+                }
+                kept.push(chapter);
+                continue;
+            }
+            log::info!("Leaving out chapter \"{}\"", chapter.title);
+            if self.excluded == ExcludedChapters::Merge {
+                match kept.last_mut() {
+                    Some(previous) => previous.end_time = previous.end_time.max(chapter.end_time),
+                    None => {
+                        pending_start.get_or_insert(chapter.start_time);
+                    }
+                }
+            }
+        }
+
+        if kept.is_empty() {
+            return Err(YtcsError::ChapterError(
+                "No chapters left after the include/exclude patterns".to_string(),
+            ));
+        }
+        Ok(kept)
+    }
+}
@@ -11,6 +11,17 @@
 //! 2. For each chapter, finds the nearest silence (within a window)
 //! 3. Adjusts the timecode towards that silence
 //!
+//! Crossfaded mixes have no real silence between tracks. For those,
+//! [`refine_chapters_with_energy`] looks at the short-term loudness around
+//! each cut instead and moves it to the quietest point, just before the next
+//! track comes in. Both strategies share the same boundary rules (see
+//! [`refine_chapters_with_silence`]).
+//!
+//! [`refine_chapters`] also reports what happened at each cut
+//! ([`BoundaryRefinement`]): how far it moved, what it snapped to, and a
+//! confidence score that the `max_delta` / `min_confidence` limits act on.
+//! [`write_manifest`] records it next to the tracks.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -31,27 +42,138 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
-use crate::chapters::Chapter;
+use crate::chapters::{Chapter, format_timestamp};
+use crate::config::RefineStrategy;
 use crate::error::{Result, YtcsError};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 /// Detected silence point in the audio
 #[derive(Debug, Clone)]
 struct SilencePoint {
     /// Position of the silence in seconds
     position: f64,
+    /// Length of the silence in seconds
+    length: f64,
 }
 
 impl SilencePoint {
     fn new(start: f64, end: f64) -> Self {
         let position = (start + end) / 2.0;
-        Self { position }
+        Self {
+            position,
+            length: end - start,
+        }
+    }
+}
+
+/// Settings for [`refine_chapters`] (the `refine_*` keys of the config).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RefineOptions {
+    pub strategy: RefineStrategy,
+    /// Search window in seconds (±) around each declared cut
+    pub window: f64,
+    /// Silence threshold in dB (silence strategy)
+    pub noise_db: f64,
+    /// Minimum silence duration in seconds (silence strategy)
+    pub min_silence: f64,
+    /// Cuts that would move further than this (seconds) keep their declared
+    /// time; 0 = no limit besides the window
+    pub max_delta: f64,
+    /// Cuts with a lower confidence (0–1) keep their declared time
+    pub min_confidence: f64,
+}
+
+impl Default for RefineOptions {
+    fn default() -> Self {
+        Self {
+            strategy: RefineStrategy::Silence,
+            window: 5.0,
+            noise_db: -35.0,
+            min_silence: 1.2,
+            max_delta: 0.0,
+            min_confidence: 0.0,
+        }
+    }
+}
+
+/// A spot proposed for one cut: a silence, or a quiet dip in the loudness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snap {
+    pub position: f64,
+    /// Length of the silence or dip in seconds
+    pub length: f64,
+    /// How much quieter (dB) the dip is than the declared cut (energy strategy)
+    pub depth_db: Option<f64>,
+}
+
+/// What refinement did with one cut between two chapters.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoundaryRefinement {
+    /// Index of the chapter starting at this cut
+    pub chapter: usize,
+    /// The cut as declared (chapters, description, …)
+    pub declared: f64,
+    /// Where the strategy wanted to move it, if it found a spot in the window
+    pub proposed: Option<f64>,
+    /// The cut that is used
+    pub refined: f64,
+    /// Length of the silence or dip found, in seconds
+    pub length: Option<f64>,
+    /// Depth of the dip found, in dB (energy strategy)
+    pub depth_db: Option<f64>,
+    /// 0 (nothing found) to 1 (a long, clear gap right at the declared time)
+    pub confidence: f64,
+    /// The proposed spot was turned down by `max_delta` / `min_confidence`
+    pub rejected: bool,
+}
+
+impl BoundaryRefinement {
+    /// Seconds the cut moved (positive = later).
+    pub fn delta(&self) -> f64 {
+        self.refined - self.declared
+    }
+
+    /// Whether the cut moved to the spot that was found.
+    pub fn snapped(&self) -> bool {
+        self.proposed.is_some() && !self.rejected
     }
 }
 
+/// Refined chapters with what happened at each cut.
+#[derive(Debug, Clone)]
+pub struct Refinement {
+    pub chapters: Vec<Chapter>,
+    /// One entry per interior cut, in order
+    pub cuts: Vec<BoundaryRefinement>,
+}
+
+impl Refinement {
+    /// Cuts that were rejected or whose confidence is below `threshold`.
+    pub fn doubtful(&self, threshold: f64) -> impl Iterator<Item = &BoundaryRefinement> {
+        self.cuts
+            .iter()
+            .filter(move |cut| cut.rejected || cut.confidence < threshold)
+    }
+}
+
+/// Confidence (0–1) in moving a cut by `delta` seconds to `snap`: half from
+/// how close it is to the declared time, half from how clear the spot is
+/// (2 s of silence, or a 20 dB dip, count as fully clear).
+pub fn snap_confidence(delta: f64, window: f64, snap: &Snap) -> f64 {
+    let closeness = (1.0 - delta.abs() / window.max(f64::EPSILON)).clamp(0.0, 1.0);
+    let clarity = match snap.depth_db {
+        Some(depth) => depth / 20.0,
+        None => snap.length / 2.0,
+    }
+    .clamp(0.0, 1.0);
+    0.5 * closeness + 0.5 * clarity
+}
+
 /// Analyzes the audio and extracts all silence points.
 ///
 /// # Arguments
@@ -153,11 +275,40 @@ const MIN_BOUNDARY_GAP: f64 = 0.05;
 /// the declared position if none is found). Pulled out of
 /// [`refine_chapters_with_silence`] so the boundary math can be unit-tested
 /// without needing ffmpeg or a real audio file.
+#[cfg(test)]
 fn compute_refined_boundaries(
     chapters: &[Chapter],
     silences: &[SilencePoint],
     window: f64,
 ) -> Vec<f64> {
+    let options = RefineOptions {
+        window,
+        ..RefineOptions::default()
+    };
+    compute_boundaries(chapters, &options, |target| {
+        silence_snap(silences, target, window)
+    })
+    .0
+}
+
+fn silence_snap(silences: &[SilencePoint], target: f64, window: f64) -> Option<Snap> {
+    find_nearest_silence(silences, target, window).map(|s| Snap {
+        position: s.position,
+        length: s.length,
+        depth_db: None,
+    })
+}
+
+/// Boundary rules shared by every strategy: `snap` proposes a new position
+/// for each declared interior cut (`None` keeps it), proposals outside the
+/// `max_delta` / `min_confidence` limits of `options` are turned down, the
+/// first start and last end stay fixed, and the result is strictly
+/// increasing. Returns the boundaries and what happened at each interior cut.
+fn compute_boundaries(
+    chapters: &[Chapter],
+    options: &RefineOptions,
+    snap: impl Fn(f64) -> Option<Snap>,
+) -> (Vec<f64>, Vec<BoundaryRefinement>) {
     let n = chapters.len();
     let first_start = chapters[0].start_time;
     let last_end = chapters[n - 1].end_time;
@@ -168,11 +319,31 @@ fn compute_refined_boundaries(
     boundaries[0] = first_start;
     boundaries[n] = last_end;
 
+    let mut cuts = Vec::with_capacity(n.saturating_sub(1));
     for i in 1..n {
         let target = (chapters[i - 1].end_time + chapters[i].start_time) / 2.0;
-        boundaries[i] = find_nearest_silence(silences, target, window)
-            .map(|s| s.position)
-            .unwrap_or(target);
+        let found = snap(target);
+        let confidence = found.map_or(0.0, |s| {
+            snap_confidence(s.position - target, options.window, &s)
+        });
+        let rejected = found.is_some_and(|s| {
+            (options.max_delta > 0.0 && (s.position - target).abs() > options.max_delta)
+                || confidence < options.min_confidence
+        });
+        boundaries[i] = match found {
+            Some(s) if !rejected => s.position,
+            _ => target,
+        };
+        cuts.push(BoundaryRefinement {
+            chapter: i,
+            declared: target,
+            proposed: found.map(|s| s.position),
+            refined: boundaries[i],
+            length: found.map(|s| s.length),
+            depth_db: found.and_then(|s| s.depth_db),
+            confidence,
+            rejected,
+        });
     }
 
     // Enforce strictly increasing boundaries without ever moving the fixed
@@ -204,7 +375,10 @@ fn compute_refined_boundaries(
         boundaries
     );
 
-    boundaries
+    for cut in &mut cuts {
+        cut.refined = boundaries[cut.chapter];
+    }
+    (boundaries, cuts)
 }
 
 /// Refines chapters using silence points.
@@ -235,21 +409,60 @@ pub fn refine_chapters_with_silence(
     noise_threshold: f64,
     min_silence_duration: f64,
 ) -> Result<Vec<Chapter>> {
-    if chapters.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    // Detect all silences once
-    let silences = detect_all_silences(audio_file, noise_threshold, min_silence_duration)?;
+    let options = RefineOptions {
+        window,
+        noise_db: noise_threshold,
+        min_silence: min_silence_duration,
+        ..RefineOptions::default()
+    };
+    Ok(refine_chapters(chapters, audio_file, &options)?.chapters)
+}
 
-    if silences.is_empty() {
-        log::warn!("No silences detected, returning original chapters");
-        return Ok(chapters.to_vec());
+/// Refines chapters with the strategy and limits of `options`, and reports
+/// what happened at every cut.
+///
+/// # Errors
+///
+/// Returns an error if ffmpeg can't analyze the audio
+pub fn refine_chapters(
+    chapters: &[Chapter],
+    audio_file: &Path,
+    options: &RefineOptions,
+) -> Result<Refinement> {
+    if chapters.is_empty() {
+        return Ok(Refinement {
+            chapters: Vec::new(),
+            cuts: Vec::new(),
+        });
     }
 
-    let boundaries = compute_refined_boundaries(chapters, &silences, window);
+    let (boundaries, cuts) = match options.strategy {
+        RefineStrategy::Silence => {
+            // Detect all silences once
+            let silences = detect_all_silences(audio_file, options.noise_db, options.min_silence)?;
+            if silences.is_empty() {
+                log::warn!("No silences detected, keeping the declared cuts");
+            }
+            compute_boundaries(chapters, options, |target| {
+                silence_snap(&silences, target, options.window)
+            })
+        }
+        RefineStrategy::Energy => {
+            let envelope = decode_envelope(audio_file)?;
+            compute_boundaries(chapters, options, |target| {
+                find_energy_dip(&envelope, target, options.window)
+            })
+        }
+    };
+    Ok(Refinement {
+        chapters: chapters_at_boundaries(chapters, &boundaries)?,
+        cuts,
+    })
+}
 
-    let refined: Vec<Chapter> = chapters
+/// Rebuilds `chapters` on refined `boundaries` (from [`compute_boundaries`]).
+fn chapters_at_boundaries(chapters: &[Chapter], boundaries: &[f64]) -> Result<Vec<Chapter>> {
+    chapters
         .iter()
         .enumerate()
         .map(|(i, chapter)| {
@@ -265,40 +478,286 @@ pub fn refine_chapters_with_silence(
                 final_end,
                 (final_end - final_start) - chapter.duration()
             );
-            Chapter::new(chapter.title.clone(), final_start, final_end)
+            Chapter::try_new(chapter.title.clone(), final_start, final_end)
         })
-        .collect();
+        .collect()
+}
 
-    Ok(refined)
+/// Sample rate the audio is decoded at for energy analysis (mono).
+const ENERGY_SAMPLE_RATE: usize = 8000;
+
+/// Length of one loudness measurement, in seconds.
+pub const ENERGY_FRAME: f64 = 0.05;
+
+/// Frames averaged when smoothing the loudness curve (0.25s).
+const ENERGY_SMOOTHING: usize = 5;
+
+/// How much quieter (dB) than the declared cut a point must be to move there.
+const MIN_DIP_DB: f64 = 3.0;
+
+/// Points this close (dB) to the quietest one count as the same dip.
+const DIP_PLATEAU_DB: f64 = 1.0;
+
+/// Penalty (dB) for a point at the edge of the window, so that among equally
+/// quiet points the one nearest the declared cut wins.
+const DISTANCE_PENALTY_DB: f64 = 3.0;
+
+/// Loudness (dB RMS) of consecutive frames of `frame_len` samples.
+pub fn rms_envelope(samples: &[f32], frame_len: usize) -> Vec<f64> {
+    samples
+        .chunks(frame_len.max(1))
+        .map(|frame| {
+            let power: f64 = frame
+                .iter()
+                .map(|&x| f64::from(x) * f64::from(x))
+                .sum::<f64>()
+                / frame.len() as f64;
+            10.0 * (power + 1e-12).log10()
+        })
+        .collect()
 }
 
-/// Prints a comparison report between original and refined chapters.
-pub fn print_refinement_report(original: &[Chapter], refined: &[Chapter]) {
-    if original.len() != refined.len() {
-        println!("⚠ Cannot compare: different chapter counts");
-        return;
+/// Finds a cut near `target` in a loudness curve (one value per
+/// [`ENERGY_FRAME`]).
+///
+/// The curve is smoothed, and the quietest point within `window` seconds is
+/// chosen (slightly favouring points near `target`). When that dip is a
+/// plateau, the cut goes at its end, where the next track sets in. Returns
+/// `None` when nothing in the window is clearly quieter than the declared cut.
+pub fn find_energy_cut(envelope: &[f64], target: f64, window: f64) -> Option<f64> {
+    find_energy_dip(envelope, target, window).map(|dip| dip.position)
+}
+
+/// [`find_energy_cut`] with the length and depth of the dip.
+pub fn find_energy_dip(envelope: &[f64], target: f64, window: f64) -> Option<Snap> {
+    if envelope.is_empty() {
+        return None;
+    }
+    let frame_of = |t: f64| ((t / ENERGY_FRAME).round().max(0.0) as usize).min(envelope.len() - 1);
+    let smoothed = |i: usize| {
+        let from = i.saturating_sub(ENERGY_SMOOTHING / 2);
+        let to = (i + ENERGY_SMOOTHING / 2 + 1).min(envelope.len());
+        envelope[from..to].iter().sum::<f64>() / (to - from) as f64
+    };
+
+    let (first, centre, last) = (
+        frame_of(target - window),
+        frame_of(target),
+        frame_of(target + window),
+    );
+    let score = |i: usize| {
+        let distance = (i as f64 - centre as f64).abs() * ENERGY_FRAME / window.max(ENERGY_FRAME);
+        smoothed(i) + DISTANCE_PENALTY_DB * distance
+    };
+    let quietest = (first..=last).min_by(|&a, &b| score(a).total_cmp(&score(b)))?;
+    let floor = smoothed(quietest);
+    let depth = smoothed(centre) - floor;
+    if depth < MIN_DIP_DB {
+        return None;
     }
 
-    let mut total_delta = 0.0_f64;
-    let mut max_delta = 0.0_f64;
-    let mut adjusted_count = 0;
+    // End of the dip: the last frame still about as quiet as its bottom
+    let in_dip = |i: &usize| smoothed(*i) - floor <= DIP_PLATEAU_DB;
+    let onset = (quietest..=last)
+        .take_while(in_dip)
+        .last()
+        .unwrap_or(quietest);
+    let dip_start = (first..=quietest)
+        .rev()
+        .take_while(in_dip)
+        .last()
+        .unwrap_or(quietest);
+    Some(Snap {
+        position: onset as f64 * ENERGY_FRAME,
+        length: (onset - dip_start + 1) as f64 * ENERGY_FRAME,
+        depth_db: Some(depth),
+    })
+}
 
-    for (orig, refn) in original.iter().zip(refined.iter()) {
-        let delta = refn.start_time - orig.start_time;
-        if delta.abs() >= 0.1 {
-            adjusted_count += 1;
-            total_delta += delta.abs();
-            max_delta = max_delta.max(delta.abs());
+/// Decodes `audio_file` to mono PCM through an ffmpeg pipe and returns its
+/// loudness curve (one value per [`ENERGY_FRAME`]).
+fn decode_envelope(audio_file: &Path) -> Result<Vec<f64>> {
+    let mut child = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(audio_file)
+        .args(["-ac", "1", "-ar"])
+        .arg(ENERGY_SAMPLE_RATE.to_string())
+        .args(["-f", "f32le", "-"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| YtcsError::AudioError(format!("Failed to run ffmpeg: {}", e)))?;
+
+    let frame_len = (ENERGY_SAMPLE_RATE as f64 * ENERGY_FRAME) as usize;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| YtcsError::AudioError("ffmpeg produced no output".to_string()))?;
+    let mut envelope = Vec::new();
+    let mut bytes = vec![0u8; frame_len * 4];
+    loop {
+        // Fill one frame; the last one may be shorter
+        let mut filled = 0;
+        while filled < bytes.len() {
+            match stdout.read(&mut bytes[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
         }
+        let samples: Vec<f32> = bytes[..filled - filled % 4]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        if !samples.is_empty() {
+            envelope.extend(rms_envelope(&samples, frame_len));
+        }
+        if filled < bytes.len() {
+            break;
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| YtcsError::AudioError(format!("ffmpeg failed: {}", e)))?;
+    if !output.status.success() {
+        return Err(YtcsError::AudioError(format!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    log::info!("Analyzed {} loudness frames", envelope.len());
+    Ok(envelope)
+}
+
+/// Refines chapters using the loudness of the audio instead of silences.
+///
+/// Meant for crossfaded mixes where tracks never fall silent: each interior
+/// cut moves to the quietest point within `window` seconds (see
+/// [`find_energy_cut`]), or stays where it was declared when there is no
+/// clear dip. The boundary rules are the same as for
+/// [`refine_chapters_with_silence`].
+///
+/// # Errors
+///
+/// Returns an error if ffmpeg can't decode the audio
+pub fn refine_chapters_with_energy(
+    chapters: &[Chapter],
+    audio_file: &Path,
+    window: f64,
+) -> Result<Vec<Chapter>> {
+    let options = RefineOptions {
+        strategy: RefineStrategy::Energy,
+        window,
+        ..RefineOptions::default()
+    };
+    Ok(refine_chapters(chapters, audio_file, &options)?.chapters)
+}
+
+/// File the refinement manifest is written to, in the album folder.
+pub const MANIFEST_FILE: &str = "ytcs-refinement.json";
+
+/// One cut as recorded in the manifest.
+#[derive(Serialize)]
+struct ManifestCut<'a> {
+    title: &'a str,
+    #[serde(flatten)]
+    cut: &'a BoundaryRefinement,
+    delta: f64,
+    low_confidence: bool,
+}
+
+#[derive(Serialize)]
+struct Manifest<'a> {
+    source: &'a str,
+    options: &'a RefineOptions,
+    cuts: Vec<ManifestCut<'a>>,
+    chapters: &'a [Chapter],
+}
+
+/// Writes the refinement of `source` to `path` as JSON: the options, every
+/// cut (flagged `low_confidence` like in [`print_refinement_report`]) and
+/// the refined chapters.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be written
+pub fn write_manifest(
+    refinement: &Refinement,
+    options: &RefineOptions,
+    source: &str,
+    path: &Path,
+) -> Result<()> {
+    let cuts = refinement
+        .cuts
+        .iter()
+        .map(|cut| ManifestCut {
+            title: refinement
+                .chapters
+                .get(cut.chapter)
+                .map_or("", |c| c.title.as_str()),
+            cut,
+            delta: cut.delta(),
+            low_confidence: cut.rejected || cut.confidence < LOW_CONFIDENCE,
+        })
+        .collect();
+    let manifest = Manifest {
+        source,
+        options,
+        cuts,
+        chapters: &refinement.chapters,
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(())
+}
+
+/// Cuts with a lower confidence are listed by [`print_refinement_report`].
+pub const LOW_CONFIDENCE: f64 = 0.5;
+
+/// Prints how many cuts moved, and lists the rejected and low-confidence ones.
+pub fn print_refinement_report(refinement: &Refinement) {
+    if crate::ui::is_output_quiet() {
+        return;
+    }
+
+    let moved: Vec<f64> = refinement
+        .cuts
+        .iter()
+        .map(|cut| cut.delta().abs())
+        .filter(|delta| *delta >= 0.1)
+        .collect();
+    if !moved.is_empty() {
+        println!(
+            "  Adjusted {}/{} cuts (avg: {:.1}s, max: {:.1}s)",
+            moved.len(),
+            refinement.cuts.len(),
+            moved.iter().sum::<f64>() / moved.len() as f64,
+            moved.iter().copied().fold(0.0_f64, f64::max)
+        );
     }
 
-    if adjusted_count > 0 {
+    for cut in refinement.doubtful(LOW_CONFIDENCE) {
+        let why = match cut.proposed {
+            Some(proposed) if cut.rejected => format!(
+                "a {:+.1}s move (confidence {:.2}) was rejected; declared time kept",
+                proposed - cut.declared,
+                cut.confidence
+            ),
+            Some(_) => format!(
+                "moved {:+.1}s with low confidence ({:.2})",
+                cut.delta(),
+                cut.confidence
+            ),
+            None => "no gap found nearby; declared time kept".to_string(),
+        };
+        let title = refinement
+            .chapters
+            .get(cut.chapter)
+            .map_or("", |c| c.title.as_str());
         println!(
-            "  Adjusted {}/{} chapters (avg: {:.1}s, max: {:.1}s)",
-            adjusted_count,
-            original.len(),
-            total_delta / adjusted_count as f64,
-            max_delta
+            "  ⚠ Cut before \"{}\" at {}: {}",
+            title,
+            format_timestamp(cut.refined),
+            why
         );
     }
 }
@@ -453,4 +912,203 @@ mod tests {
         let boundaries = compute_refined_boundaries(&chapters, &silences, 5.0);
         assert_contiguous(&chapters, &boundaries);
     }
+
+    /// A loudness curve at -10 dB with a quieter stretch from `from` to `to`
+    /// seconds (a crossfade without real silence).
+    fn envelope_with_dip(seconds: f64, from: f64, to: f64, dip_db: f64) -> Vec<f64> {
+        (0..(seconds / ENERGY_FRAME) as usize)
+            .map(|i| {
+                let t = i as f64 * ENERGY_FRAME;
+                if (from..=to).contains(&t) {
+                    dip_db
+                } else {
+                    -10.0
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rms_envelope_levels() {
+        let mut samples = vec![1.0_f32; 400];
+        samples.extend(vec![0.1_f32; 400]);
+        samples.extend(vec![0.0_f32; 200]);
+        let envelope = rms_envelope(&samples, 400);
+        assert_eq!(envelope.len(), 3);
+        assert!(envelope[0].abs() < 1e-6);
+        assert!((envelope[1] + 20.0).abs() < 1e-3);
+        assert!(envelope[2] < -100.0);
+    }
+
+    #[test]
+    fn test_find_energy_cut_moves_to_end_of_dip() {
+        let envelope = envelope_with_dip(60.0, 29.5, 30.5, -25.0);
+        let cut = find_energy_cut(&envelope, 28.0, 5.0).unwrap();
+        assert!((30.3..=30.5).contains(&cut), "{}", cut);
+
+        // Outside the window the dip is not considered
+        assert_eq!(find_energy_cut(&envelope, 20.0, 5.0), None);
+        // Already at the quietest point: nothing to move
+        assert_eq!(find_energy_cut(&envelope, 30.0, 5.0), None);
+    }
+
+    #[test]
+    fn test_find_energy_cut_ignores_shallow_dips() {
+        let envelope = envelope_with_dip(60.0, 29.5, 30.5, -11.5);
+        assert_eq!(find_energy_cut(&envelope, 28.0, 5.0), None);
+        assert_eq!(find_energy_cut(&[], 28.0, 5.0), None);
+    }
+
+    #[test]
+    fn test_energy_boundaries_keep_invariants() {
+        let chapters = vec![
+            Chapter::new("Track 1".to_string(), 0.0, 28.0),
+            Chapter::new("Track 2".to_string(), 28.0, 55.0),
+            Chapter::new("Track 3".to_string(), 55.0, 60.0),
+        ];
+        let envelope = envelope_with_dip(60.0, 29.5, 30.5, -25.0);
+        let (boundaries, cuts) =
+            compute_boundaries(&chapters, &RefineOptions::default(), |target| {
+                find_energy_dip(&envelope, target, 5.0)
+            });
+        assert_contiguous(&chapters, &boundaries);
+        assert!(boundaries[1] > 30.0);
+        assert_eq!(boundaries[2], 55.0);
+        assert!(cuts[0].snapped());
+        assert!(!cuts[1].snapped());
+    }
+
+    #[test]
+    fn test_find_energy_dip_reports_length_and_depth() {
+        let envelope = envelope_with_dip(60.0, 29.5, 30.5, -25.0);
+        let dip = find_energy_dip(&envelope, 28.0, 5.0).unwrap();
+        assert!((0.5..=1.1).contains(&dip.length), "{}", dip.length);
+        assert!(dip.depth_db.unwrap() > 10.0, "{:?}", dip.depth_db);
+    }
+
+    fn snap(position: f64, length: f64) -> Snap {
+        Snap {
+            position,
+            length,
+            depth_db: None,
+        }
+    }
+
+    #[test]
+    fn test_snap_confidence_prefers_close_clear_gaps() {
+        let close_long = snap_confidence(0.5, 5.0, &snap(30.5, 2.0));
+        let far_short = snap_confidence(4.5, 5.0, &snap(34.5, 0.2));
+        assert!(close_long > 0.9, "{}", close_long);
+        assert!(far_short < 0.2, "{}", far_short);
+        let deep = Snap {
+            depth_db: Some(20.0),
+            ..snap(30.0, 0.1)
+        };
+        assert_eq!(snap_confidence(0.0, 5.0, &deep), 1.0);
+    }
+
+    fn two_tracks() -> Vec<Chapter> {
+        vec![
+            Chapter::new("Track 1".to_string(), 0.0, 30.0),
+            Chapter::new("Track 2".to_string(), 30.0, 60.0),
+        ]
+    }
+
+    #[test]
+    fn test_boundaries_report_each_cut() {
+        let silences = vec![SilencePoint::new(32.0, 34.0)];
+        let (boundaries, cuts) =
+            compute_boundaries(&two_tracks(), &RefineOptions::default(), |t| {
+                silence_snap(&silences, t, 5.0)
+            });
+        assert_eq!(boundaries[1], 33.0);
+        assert_eq!(cuts.len(), 1);
+        let cut = &cuts[0];
+        assert_eq!((cut.chapter, cut.declared, cut.refined), (1, 30.0, 33.0));
+        assert_eq!(cut.delta(), 3.0);
+        assert_eq!(cut.length, Some(2.0));
+        assert!(cut.snapped() && !cut.rejected);
+        assert!((cut.confidence - 0.7).abs() < 1e-9, "{}", cut.confidence);
+
+        let (_, cuts) = compute_boundaries(&two_tracks(), &RefineOptions::default(), |_| None);
+        assert_eq!((cuts[0].proposed, cuts[0].confidence), (None, 0.0));
+    }
+
+    #[test]
+    fn test_limits_keep_declared_cut() {
+        let silences = vec![SilencePoint::new(32.0, 34.0)];
+        for options in [
+            RefineOptions {
+                max_delta: 2.0,
+                ..RefineOptions::default()
+            },
+            RefineOptions {
+                min_confidence: 0.8,
+                ..RefineOptions::default()
+            },
+        ] {
+            let (boundaries, cuts) =
+                compute_boundaries(&two_tracks(), &options, |t| silence_snap(&silences, t, 5.0));
+            assert_eq!(boundaries[1], 30.0);
+            assert!(cuts[0].rejected && !cuts[0].snapped());
+            assert_eq!(cuts[0].proposed, Some(33.0));
+            assert_eq!(cuts[0].delta(), 0.0);
+        }
+
+        let lenient = RefineOptions {
+            max_delta: 3.0,
+            min_confidence: 0.6,
+            ..RefineOptions::default()
+        };
+        let (boundaries, _) =
+            compute_boundaries(&two_tracks(), &lenient, |t| silence_snap(&silences, t, 5.0));
+        assert_eq!(boundaries[1], 33.0);
+    }
+
+    #[test]
+    fn test_refinement_doubtful_cuts() {
+        let silences = vec![SilencePoint::new(29.0, 31.0), SilencePoint::new(64.9, 65.1)];
+        let chapters = vec![
+            Chapter::new("Track 1".to_string(), 0.0, 30.0),
+            Chapter::new("Track 2".to_string(), 30.0, 60.0),
+            Chapter::new("Track 3".to_string(), 60.0, 90.0),
+            Chapter::new("Track 4".to_string(), 90.0, 120.0),
+        ];
+        let (boundaries, cuts) = compute_boundaries(&chapters, &RefineOptions::default(), |t| {
+            silence_snap(&silences, t, 5.0)
+        });
+        let refinement = Refinement {
+            chapters: chapters_at_boundaries(&chapters, &boundaries).unwrap(),
+            cuts,
+        };
+        let doubtful: Vec<usize> = refinement
+            .doubtful(LOW_CONFIDENCE)
+            .map(|cut| cut.chapter)
+            .collect();
+        // A short silence 5s away, and no silence at all
+        assert_eq!(doubtful, vec![2, 3]);
+
+        let dir = std::env::temp_dir().join(format!("ytcs_manifest_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(MANIFEST_FILE);
+        write_manifest(
+            &refinement,
+            &RefineOptions::default(),
+            "https://youtu.be/x",
+            &path,
+        )
+        .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(json["source"], "https://youtu.be/x");
+        assert_eq!(json["options"]["strategy"], "silence");
+        let cuts = json["cuts"].as_array().unwrap();
+        assert_eq!(cuts.len(), 3);
+        assert_eq!(cuts[0]["title"], "Track 2");
+        assert_eq!(cuts[0]["low_confidence"], false);
+        assert_eq!(cuts[2]["proposed"], serde_json::Value::Null);
+        assert_eq!(cuts[2]["low_confidence"], true);
+        assert_eq!(json["chapters"].as_array().unwrap().len(), 4);
+    }
 }
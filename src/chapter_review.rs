@@ -0,0 +1,509 @@
+//! Reviewing and editing the chapter plan before splitting.
+//!
+//! A [`ChapterReview`] holds the chapters about to be split together with the
+//! declared start of each one, so the interactive review can show how far
+//! refinement (or a manual shift) moved every cut. Edits are parsed from short
+//! commands by [`parse_review_command`] and applied with
+//! [`ChapterReview::apply`]; every edit can be undone.
+//!
+//! [`edit_chapters_in_editor`] is the lighter alternative: the plan is written
+//! as a `HH:MM:SS Title` tracklist, opened in `$VISUAL` / `$EDITOR`, and read
+//! back with [`parse_chapter_plan`].
+
+use crate::chapters::{Chapter, format_timestamp, parse_timestamp};
+use crate::chapters_from_description::parse_chapters_from_description;
+use crate::error::{Result, YtcsError};
+use crate::temp_file::TempFile;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Boundaries closer than this (seconds) are treated as the same cut.
+const SAME_CUT: f64 = 0.05;
+
+/// Shortest chapter an edit may leave behind, in seconds.
+const MIN_CHAPTER: f64 = 1.0;
+
+/// How a boundary is moved by [`ChapterEdit::Shift`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shift {
+    /// Move by this many seconds (negative = earlier)
+    By(f64),
+    /// Move to this position in seconds
+    To(f64),
+}
+
+/// One edit to the chapter plan. Indices are 0-based.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChapterEdit {
+    /// Give a chapter a new title
+    Rename { index: usize, title: String },
+    /// Join a chapter with the next one (the first title is kept)
+    Merge { index: usize },
+    /// Cut a chapter in two at `at` seconds; the second part gets `title`
+    /// (or the chapter's title with " (2)" appended)
+    Split {
+        index: usize,
+        at: f64,
+        title: Option<String>,
+    },
+    /// Drop a chapter; its audio is not exported
+    Delete { index: usize },
+    /// Move the start of a chapter, and the end of the previous one when
+    /// the two share the cut
+    Shift { index: usize, shift: Shift },
+}
+
+/// A line typed at the review prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewCommand {
+    Edit(ChapterEdit),
+    Undo,
+    Help,
+    /// Accept the plan and start splitting
+    Done,
+    /// Stop without splitting
+    Abort,
+}
+
+/// Parses a review command (chapter numbers are 1-based):
+///
+/// - `r N <title>` rename, `m N` merge N with N+1, `d N` delete
+/// - `s N <time> [title]` split N at an absolute time
+/// - `b N <+secs|-secs|time>` shift the start of N
+/// - `u` undo, `?` help, empty / `ok` accept, `q` abort
+///
+/// # Errors
+///
+/// Returns a `ChapterError` describing what is wrong with the command
+pub fn parse_review_command(input: &str) -> Result<ReviewCommand> {
+    let input = input.trim();
+    let (verb, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let rest = rest.trim();
+    let invalid = |msg: &str| YtcsError::ChapterError(msg.to_string());
+
+    // `N` and the remainder after it
+    let number = |usage: &str| -> Result<(usize, &str)> {
+        let (n, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let n = n
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| invalid(&format!("Usage: {}", usage)))?;
+        Ok((n - 1, tail.trim()))
+    };
+
+    let edit = match verb.to_lowercase().as_str() {
+        "" | "ok" | "y" | "yes" => return Ok(ReviewCommand::Done),
+        "q" | "quit" | "abort" => return Ok(ReviewCommand::Abort),
+        "u" | "undo" => return Ok(ReviewCommand::Undo),
+        "?" | "h" | "help" => return Ok(ReviewCommand::Help),
+        "r" | "rename" => {
+            let (index, title) = number("r N <new title>")?;
+            if title.is_empty() {
+                return Err(invalid("Usage: r N <new title>"));
+            }
+            ChapterEdit::Rename {
+                index,
+                title: title.to_string(),
+            }
+        }
+        "m" | "merge" => ChapterEdit::Merge {
+            index: number("m N (joins N and N+1)")?.0,
+        },
+        "d" | "delete" => ChapterEdit::Delete {
+            index: number("d N")?.0,
+        },
+        "s" | "split" => {
+            let (index, tail) = number("s N <time> [title]")?;
+            let (at, title) = tail.split_once(char::is_whitespace).unwrap_or((tail, ""));
+            let at = parse_timestamp(at).map_err(|_| invalid("Usage: s N <time> [title]"))?;
+            ChapterEdit::Split {
+                index,
+                at,
+                title: Some(title.trim())
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string),
+            }
+        }
+        "b" | "shift" => {
+            let (index, amount) = number("b N <+secs|-secs|time>")?;
+            let usage = || invalid("Usage: b N <+secs|-secs|time>");
+            let shift = if let Some(secs) = amount.strip_prefix('+') {
+                Shift::By(secs.parse::<f64>().map_err(|_| usage())?)
+            } else if let Some(secs) = amount.strip_prefix('-') {
+                Shift::By(-secs.parse::<f64>().map_err(|_| usage())?)
+            } else {
+                Shift::To(parse_timestamp(amount).map_err(|_| usage())?)
+            };
+            ChapterEdit::Shift { index, shift }
+        }
+        _ => return Err(invalid("Unknown command (type ? for help)")),
+    };
+    Ok(ReviewCommand::Edit(edit))
+}
+
+/// The chapter plan under review.
+#[derive(Debug, Clone)]
+pub struct ChapterReview {
+    chapters: Vec<Chapter>,
+    /// Declared start of each chapter (`None` for chapters created by a split)
+    declared: Vec<Option<f64>>,
+    history: Vec<(Vec<Chapter>, Vec<Option<f64>>)>,
+}
+
+impl ChapterReview {
+    /// Starts a review of `chapters`. `declared` are the chapters before
+    /// refinement; when the counts differ no deltas are shown.
+    pub fn new(declared: &[Chapter], chapters: Vec<Chapter>) -> Self {
+        let declared = if declared.len() == chapters.len() {
+            declared.iter().map(|c| Some(c.start_time)).collect()
+        } else {
+            vec![None; chapters.len()]
+        };
+        Self {
+            chapters,
+            declared,
+            history: Vec::new(),
+        }
+    }
+
+    /// The chapters as currently edited.
+    pub fn chapters(&self) -> &[Chapter] {
+        &self.chapters
+    }
+
+    /// How far chapter `index` starts from its declared start, in seconds.
+    pub fn delta(&self, index: usize) -> Option<f64> {
+        let declared = self.declared.get(index).copied().flatten()?;
+        Some(self.chapters[index].start_time - declared)
+    }
+
+    /// Reverts the last edit; returns false if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some((chapters, declared)) => {
+                self.chapters = chapters;
+                self.declared = declared;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Ends the review, returning the edited chapters.
+    pub fn into_chapters(self) -> Vec<Chapter> {
+        self.chapters
+    }
+
+    /// Applies one edit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChapterError` (and leaves the plan unchanged) if the
+    /// chapter doesn't exist or the edit would leave a chapter shorter than
+    /// one second, or no chapter at all
+    pub fn apply(&mut self, edit: &ChapterEdit) -> Result<()> {
+        let snapshot = (self.chapters.clone(), self.declared.clone());
+        let index = match edit {
+            ChapterEdit::Rename { index, .. }
+            | ChapterEdit::Merge { index }
+            | ChapterEdit::Split { index, .. }
+            | ChapterEdit::Delete { index }
+            | ChapterEdit::Shift { index, .. } => *index,
+        };
+        if index >= self.chapters.len() {
+            return Err(YtcsError::ChapterError(format!(
+                "There is no chapter {} (1-{})",
+                index + 1,
+                self.chapters.len()
+            )));
+        }
+
+        match edit {
+            ChapterEdit::Rename { title, .. } => {
+                self.chapters[index].title = title.trim().to_string();
+            }
+            ChapterEdit::Merge { .. } => {
+                if index + 1 >= self.chapters.len() {
+                    return Err(YtcsError::ChapterError(
+                        "The last chapter has no next chapter to merge with".to_string(),
+                    ));
+                }
+                let next = self.chapters.remove(index + 1);
+                self.declared.remove(index + 1);
+                let chapter = &mut self.chapters[index];
+                chapter.end_time = chapter.end_time.max(next.end_time);
+            }
+            ChapterEdit::Split { at, title, .. } => {
+                let chapter = &self.chapters[index];
+                if *at - chapter.start_time < MIN_CHAPTER || chapter.end_time - *at < MIN_CHAPTER {
+                    return Err(YtcsError::ChapterError(format!(
+                        "Split point must fall inside chapter {} ({} - {})",
+                        index + 1,
+                        format_timestamp(chapter.start_time),
+                        format_timestamp(chapter.end_time)
+                    )));
+                }
+                let title = title
+                    .clone()
+                    .unwrap_or_else(|| format!("{} (2)", chapter.title));
+                let second = Chapter::try_new(title, *at, chapter.end_time)?;
+                self.chapters[index].end_time = *at;
+                self.chapters.insert(index + 1, second);
+                self.declared.insert(index + 1, None);
+            }
+            ChapterEdit::Delete { .. } => {
+                if self.chapters.len() == 1 {
+                    return Err(YtcsError::ChapterError(
+                        "Cannot delete the only chapter".to_string(),
+                    ));
+                }
+                self.chapters.remove(index);
+                self.declared.remove(index);
+            }
+            ChapterEdit::Shift { shift, .. } => {
+                let chapter = &self.chapters[index];
+                let target = match shift {
+                    Shift::By(secs) => chapter.start_time + secs,
+                    Shift::To(at) => *at,
+                };
+                // A shared cut moves both neighbours; a gap only moves this start.
+                let previous = index.checked_sub(1).map(|p| &self.chapters[p]);
+                let shared =
+                    previous.is_some_and(|p| (p.end_time - chapter.start_time).abs() < SAME_CUT);
+                let lower = match previous {
+                    Some(p) if shared => p.start_time + MIN_CHAPTER,
+                    Some(p) => p.end_time,
+                    None => 0.0,
+                };
+                let upper = chapter.end_time - MIN_CHAPTER;
+                if target < lower || target > upper {
+                    return Err(YtcsError::ChapterError(format!(
+                        "Chapter {} can only start between {} and {}",
+                        index + 1,
+                        format_timestamp(lower),
+                        format_timestamp(upper.max(lower))
+                    )));
+                }
+                if shared {
+                    self.chapters[index - 1].end_time = target;
+                }
+                self.chapters[index].start_time = target;
+            }
+        }
+        self.history.push(snapshot);
+        Ok(())
+    }
+}
+
+/// Leading timestamp of a tracklist line, as accepted by
+/// [`parse_chapters_from_description`].
+static RE_LINE_TIMESTAMP: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\[?(\d{1,2}:\d{2}(?::\d{2})?)\]?").unwrap());
+
+/// Formats seconds as `HH:MM:SS` (whole seconds).
+fn format_hms(seconds: f64) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        (total % 3600) / 60,
+        total % 60
+    )
+}
+
+/// Writes the chapter plan as one `HH:MM:SS Title` line per chapter, under a
+/// `#` header naming the video and its duration.
+pub fn format_chapter_plan(chapters: &[Chapter], video_title: &str, duration: f64) -> String {
+    let mut text = format!(
+        "# {}\n\
+         # Duration: {} ({})\n\
+         #\n\
+         # One chapter per line: HH:MM:SS Title. Lines starting with # are ignored.\n\
+         # Starts must increase and stay below the duration; each chapter ends where\n\
+         # the next one starts. Delete every line to abort.\n",
+        video_title,
+        format_hms(duration),
+        crate::utils::format_duration(duration)
+    );
+    for chapter in chapters {
+        text.push_str(&format!(
+            "{} {}\n",
+            format_hms(chapter.start_time),
+            chapter.title
+        ));
+    }
+    text
+}
+
+/// Reads back an edited tracklist written by [`format_chapter_plan`].
+///
+/// Lines whose whole-second start matches a chapter of `previous` keep that
+/// chapter's exact start, so refined cuts survive an untouched line. Such a
+/// chapter also keeps its end when `previous` had a gap after it (audio left
+/// out by the chapter filter), so the gap is not merged back; every other
+/// chapter ends where the next one starts, the last one at `duration`.
+///
+/// # Errors
+///
+/// Returns a `ChapterError` naming the offending line if a line has no
+/// leading timestamp or a title, if the starts are not strictly increasing,
+/// or if a start is not below `duration`; also if fewer than two chapters
+/// remain
+pub fn parse_chapter_plan(text: &str, duration: f64, previous: &[Chapter]) -> Result<Vec<Chapter>> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    let mut last_start: Option<f64> = None;
+    for (number, line) in &lines {
+        let invalid = |msg: String| YtcsError::ChapterError(format!("line {}: {}", number, msg));
+        let caps = RE_LINE_TIMESTAMP
+            .captures(line)
+            .ok_or_else(|| invalid("expected \"HH:MM:SS Title\"".to_string()))?;
+        let start = parse_timestamp(&caps[1]).map_err(|e| invalid(e.to_string()))?;
+        if line[caps[0].len()..]
+            .trim_start_matches([' ', '-', '–', '—', ':'])
+            .trim()
+            .len()
+            < 2
+        {
+            return Err(invalid("missing title".to_string()));
+        }
+        if start >= duration {
+            return Err(invalid(format!(
+                "{} is past the end of the video ({})",
+                &caps[1],
+                format_hms(duration)
+            )));
+        }
+        if let Some(last) = last_start
+            && start <= last
+        {
+            return Err(invalid(format!(
+                "{} does not come after the previous chapter ({})",
+                &caps[1],
+                format_hms(last)
+            )));
+        }
+        last_start = Some(start);
+    }
+
+    let body: Vec<&str> = lines.iter().map(|(_, line)| *line).collect();
+    let mut chapters = parse_chapters_from_description(&body.join("\n"), duration)?;
+    if chapters.len() != lines.len() {
+        return Err(YtcsError::ChapterError(
+            "every chapter must be at least 1 second long".to_string(),
+        ));
+    }
+
+    let mut origins = vec![None; chapters.len()];
+    for i in 0..chapters.len() {
+        let start = chapters[i].start_time;
+        if let Some(k) = previous.iter().position(|c| {
+            c.start_time.floor() == start && (i == 0 || c.start_time > chapters[i - 1].start_time)
+        }) {
+            chapters[i].start_time = previous[k].start_time;
+            origins[i] = Some(k);
+        }
+    }
+    for i in 0..chapters.len() {
+        let next_start = chapters.get(i + 1).map_or(duration, |c| c.start_time);
+        chapters[i].end_time = match origins[i] {
+            Some(k)
+                if previous
+                    .get(k + 1)
+                    .is_none_or(|next| previous[k].end_time < next.start_time) =>
+            {
+                previous[k].end_time.min(next_start)
+            }
+            _ => next_start,
+        };
+    }
+    Ok(chapters)
+}
+
+/// The user's editor: `$VISUAL`, then `$EDITOR`, then a platform default.
+fn editor_command() -> String {
+    std::env::var("VISUAL")
+        .ok()
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// Opens the chapter plan in the user's editor and returns the edited chapters.
+///
+/// `duration` is the length of the audio the chapters will be cut from. The
+/// tracklist is written to a uniquely named file in the system temp directory
+/// (removed afterwards). An invalid list is reopened with the error on top
+/// until it parses.
+///
+/// # Errors
+///
+/// Returns an error if the editor can't be started or exits with a failure,
+/// or if the user deletes every chapter line
+pub fn edit_chapters_in_editor(
+    chapters: &[Chapter],
+    video_title: &str,
+    duration: f64,
+) -> Result<Vec<Chapter>> {
+    static EDIT_COUNT: AtomicUsize = AtomicUsize::new(0);
+    let file = TempFile::new(&std::env::temp_dir().join(format!(
+        "ytcs-chapters-{}-{}.txt",
+        std::process::id(),
+        EDIT_COUNT.fetch_add(1, Ordering::Relaxed)
+    )));
+    let mut text = format_chapter_plan(chapters, video_title, duration);
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let args: Vec<&str> = parts.collect();
+
+    loop {
+        std::fs::write(file.path(), &text)?;
+        let status = Command::new(program)
+            .args(&args)
+            .arg(file.path())
+            .status()
+            .map_err(|e| YtcsError::Other(format!("Cannot start editor \"{}\": {}", editor, e)))?;
+        if !status.success() {
+            return Err(YtcsError::Other(format!(
+                "Editor \"{}\" exited with {}",
+                editor, status
+            )));
+        }
+
+        let edited = std::fs::read_to_string(file.path())?;
+        if edited
+            .lines()
+            .all(|l| l.trim().is_empty() || l.trim_start().starts_with('#'))
+        {
+            return Err(YtcsError::Other(
+                "Chapter edit aborted (empty chapter list)".to_string(),
+            ));
+        }
+        match parse_chapter_plan(&edited, duration, chapters) {
+            Ok(parsed) => return Ok(parsed),
+            Err(e) => {
+                log::warn!("Edited chapter list rejected: {}", e);
+                let kept: Vec<&str> = edited
+                    .lines()
+                    .filter(|l| !l.starts_with("# ERROR:"))
+                    .collect();
+                text = format!("# ERROR: {}\n{}\n", e, kept.join("\n"));
+            }
+        }
+    }
+}
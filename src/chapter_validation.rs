@@ -0,0 +1,337 @@
+//! Sanity checks on a chapter list before it is split.
+//!
+//! Timestamps read from a description or a comment can be garbage: times
+//! from a lyrics block, the same time listed twice, or chapters past the end
+//! of the audio (the metadata duration and the downloaded file don't always
+//! agree). [`validate_chapters`] reports such problems as
+//! [`ChapterWarning`]s, and [`sanitize_chapters`] drops duplicates, refuses
+//! out-of-order lists and trims the list to the audio that was actually
+//! downloaded, so no two tracks overlap.
+
+use crate::chapters::{Chapter, format_timestamp};
+use crate::error::{Result, YtcsError};
+use std::fmt;
+
+/// Tracks shorter than this (seconds) are probably stray timestamps.
+pub const TINY_TRACK: f64 = 5.0;
+
+/// Tracks longer than this (seconds) probably swallowed a missing timestamp.
+pub const HUGE_TRACK: f64 = 30.0 * 60.0;
+
+/// Difference (seconds) between the chapters and the audio length that is
+/// not worth a warning.
+pub const DURATION_TOLERANCE: f64 = 2.0;
+
+/// Two chapters starting this close (seconds) are duplicates.
+const DUPLICATE_GAP: f64 = 0.5;
+
+/// A chapter starting less than this (seconds) before the audio ends is dropped.
+const MIN_TAIL: f64 = 1.0;
+
+/// A problem found in a chapter list. `index` is 0-based.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChapterWarning {
+    /// Negative or non-finite start, or an end not after the start
+    InvalidRange {
+        index: usize,
+        title: String,
+        start: f64,
+        end: f64,
+    },
+    /// Starts before the previous chapter
+    OutOfOrder {
+        index: usize,
+        title: String,
+        start: f64,
+        previous_start: f64,
+    },
+    /// Starts at (almost) the same time as the previous chapter
+    DuplicateStart {
+        index: usize,
+        title: String,
+        start: f64,
+    },
+    TooShort {
+        index: usize,
+        title: String,
+        duration: f64,
+    },
+    TooLong {
+        index: usize,
+        title: String,
+        duration: f64,
+    },
+    /// Chapters starting at or after the end of the audio
+    PastAudioEnd { count: usize, audio_duration: f64 },
+    /// The chapters run on after the audio ends
+    EndsAfterAudio {
+        chapter_end: f64,
+        audio_duration: f64,
+    },
+    /// The audio goes on after the last chapter ends
+    EndsBeforeAudio {
+        chapter_end: f64,
+        audio_duration: f64,
+    },
+}
+
+impl fmt::Display for ChapterWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRange {
+                index,
+                title,
+                start,
+                end,
+            } => write!(
+                f,
+                "chapter {} \"{}\" has an invalid time range ({:.1}s to {:.1}s)",
+                index + 1,
+                title,
+                start,
+                end
+            ),
+            Self::OutOfOrder {
+                index,
+                title,
+                start,
+                previous_start,
+            } => write!(
+                f,
+                "chapter {} \"{}\" starts at {}, before the previous chapter ({}); \
+                 the timestamps may come from lyrics or notes",
+                index + 1,
+                title,
+                format_timestamp(*start),
+                format_timestamp(*previous_start)
+            ),
+            Self::DuplicateStart {
+                index,
+                title,
+                start,
+            } => write!(
+                f,
+                "chapter {} \"{}\" starts at the same time as the previous one ({}) and will be dropped",
+                index + 1,
+                title,
+                format_timestamp(*start)
+            ),
+            Self::TooShort {
+                index,
+                title,
+                duration,
+            } => write!(
+                f,
+                "chapter {} \"{}\" is only {:.1}s long",
+                index + 1,
+                title,
+                duration
+            ),
+            Self::TooLong {
+                index,
+                title,
+                duration,
+            } => write!(
+                f,
+                "chapter {} \"{}\" is {} long; a timestamp may be missing",
+                index + 1,
+                title,
+                format_timestamp(*duration)
+            ),
+            Self::PastAudioEnd {
+                count,
+                audio_duration,
+            } => write!(
+                f,
+                "{} chapter{} start after the audio ends ({}) and will be dropped",
+                count,
+                if *count == 1 { "" } else { "s" },
+                format_timestamp(*audio_duration)
+            ),
+            Self::EndsAfterAudio {
+                chapter_end,
+                audio_duration,
+            } => write!(
+                f,
+                "the chapters end at {} but the audio at {}; the last track will be shortened",
+                format_timestamp(*chapter_end),
+                format_timestamp(*audio_duration)
+            ),
+            Self::EndsBeforeAudio {
+                chapter_end,
+                audio_duration,
+            } => write!(
+                f,
+                "the chapters end at {} but the audio runs to {}; the rest is not part of any track",
+                format_timestamp(*chapter_end),
+                format_timestamp(*audio_duration)
+            ),
+        }
+    }
+}
+
+/// Checks the order, spacing and lengths of `chapters`, and, given the
+/// length of the downloaded audio (`audio::get_audio_duration`), whether
+/// they fit it.
+///
+/// Tracks over [`HUGE_TRACK`] are only reported when there are several
+/// chapters; a single chapter may well span the whole video.
+pub fn validate_chapters(chapters: &[Chapter], audio_duration: Option<f64>) -> Vec<ChapterWarning> {
+    let mut warnings = Vec::new();
+
+    for (index, chapter) in chapters.iter().enumerate() {
+        let title = chapter.title.clone();
+        let (start, end) = (chapter.start_time, chapter.end_time);
+        if !start.is_finite() || !end.is_finite() || start < 0.0 || end <= start {
+            warnings.push(ChapterWarning::InvalidRange {
+                index,
+                title,
+                start,
+                end,
+            });
+            continue;
+        }
+
+        if let Some(previous) = index.checked_sub(1).map(|i| &chapters[i]) {
+            if (start - previous.start_time).abs() < DUPLICATE_GAP {
+                warnings.push(ChapterWarning::DuplicateStart {
+                    index,
+                    title: title.clone(),
+                    start,
+                });
+            } else if start < previous.start_time {
+                warnings.push(ChapterWarning::OutOfOrder {
+                    index,
+                    title: title.clone(),
+                    start,
+                    previous_start: previous.start_time,
+                });
+            }
+        }
+
+        let duration = chapter.duration();
+        if duration < TINY_TRACK {
+            warnings.push(ChapterWarning::TooShort {
+                index,
+                title,
+                duration,
+            });
+        } else if duration > HUGE_TRACK && chapters.len() > 1 {
+            warnings.push(ChapterWarning::TooLong {
+                index,
+                title,
+                duration,
+            });
+        }
+    }
+
+    let Some(audio_duration) = audio_duration.filter(|d| d.is_finite() && *d > 0.0) else {
+        return warnings;
+    };
+    let past_end = chapters
+        .iter()
+        .filter(|c| c.start_time + MIN_TAIL >= audio_duration)
+        .count();
+    if past_end > 0 {
+        warnings.push(ChapterWarning::PastAudioEnd {
+            count: past_end,
+            audio_duration,
+        });
+    }
+    let chapter_end = chapters
+        .iter()
+        .map(|c| c.end_time)
+        .filter(|end| end.is_finite())
+        .fold(f64::NEG_INFINITY, f64::max);
+    if chapter_end > audio_duration + DURATION_TOLERANCE {
+        warnings.push(ChapterWarning::EndsAfterAudio {
+            chapter_end,
+            audio_duration,
+        });
+    } else if chapter_end.is_finite() && chapter_end < audio_duration - DURATION_TOLERANCE {
+        warnings.push(ChapterWarning::EndsBeforeAudio {
+            chapter_end,
+            audio_duration,
+        });
+    }
+
+    warnings
+}
+
+/// Makes `chapters` safe to split: drops chapters with an invalid range and
+/// duplicates of the previous start, ends each chapter no later than the
+/// next one starts, and, given the audio length, drops chapters starting
+/// (almost) at its end and ends the rest no later than the audio does.
+///
+/// # Errors
+///
+/// Returns a `ChapterError` if the chapters are out of order (they can't be
+/// told apart from stray timestamps), or if no chapter is left
+pub fn sanitize_chapters(
+    chapters: &[Chapter],
+    audio_duration: Option<f64>,
+) -> Result<Vec<Chapter>> {
+    sanitize_chapters_or_reorder(chapters, audio_duration, |_| Ok(None))
+}
+
+/// Like [`sanitize_chapters`], but out-of-order chapters are first handed,
+/// as given, to `reorder` (the `--edit-chapters` editor). A list it returns
+/// is sanitized instead; `None` means the order can't be fixed here.
+///
+/// # Errors
+///
+/// Returns the error of `reorder`, or a `ChapterError` as
+/// [`sanitize_chapters`] does
+pub fn sanitize_chapters_or_reorder<F>(
+    chapters: &[Chapter],
+    audio_duration: Option<f64>,
+    reorder: F,
+) -> Result<Vec<Chapter>>
+where
+    F: FnOnce(&[Chapter]) -> Result<Option<Vec<Chapter>>>,
+{
+    let mut sanitized: Vec<Chapter> = Vec::with_capacity(chapters.len());
+    for chapter in chapters {
+        let (start, end) = (chapter.start_time, chapter.end_time);
+        if !start.is_finite() || !end.is_finite() || start < 0.0 || end <= start {
+            continue;
+        }
+        if audio_duration.is_some_and(|d| start + MIN_TAIL >= d) {
+            continue;
+        }
+        if let Some(previous) = sanitized.last_mut() {
+            if (start - previous.start_time).abs() < DUPLICATE_GAP {
+                continue;
+            }
+            if start < previous.start_time {
+                if let Some(reordered) = reorder(chapters)? {
+                    return sanitize_chapters(&reordered, audio_duration);
+                }
+                return Err(YtcsError::ChapterError(format!(
+                    "Chapter \"{}\" starts at {}, before \"{}\" ({}); fix the order with \
+                     --edit-chapters or a chapters= file",
+                    chapter.title,
+                    format_timestamp(start),
+                    previous.title,
+                    format_timestamp(previous.start_time)
+                )));
+            }
+            previous.end_time = previous.end_time.min(start);
+        }
+        sanitized.push(Chapter {
+            end_time: audio_duration.map_or(end, |d| end.min(d)),
+            ..chapter.clone()
+        });
+    }
+
+    if sanitized.is_empty() {
+        return Err(YtcsError::ChapterError(match audio_duration {
+            Some(d) => format!(
+                "No chapter starts before the end of the audio ({})",
+                format_timestamp(d)
+            ),
+            None => "No chapter has a valid time range".to_string(),
+        }));
+    }
+    Ok(sanitized)
+}
@@ -18,7 +18,9 @@ pub struct Chapter {
 }
 
 impl Chapter {
-    /// Creates a new chapter.
+    /// Creates a new chapter from a range known to be valid (tests, examples).
+    /// Times read from metadata, descriptions or user input go through
+    /// [`Chapter::try_new`] instead.
     ///
     /// # Arguments
     ///
@@ -49,6 +51,32 @@ impl Chapter {
         }
     }
 
+    /// Creates a new chapter, rejecting an invalid time range.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChapterError` if start_time < 0, or if end_time <= start_time
+    /// (or either isn't a number)
+    pub fn try_new(title: String, start_time: f64, end_time: f64) -> Result<Self> {
+        if !start_time.is_finite() || !end_time.is_finite() || start_time < 0.0 {
+            return Err(YtcsError::ChapterError(format!(
+                "Chapter \"{}\" has an invalid start ({}) or end ({})",
+                title, start_time, end_time
+            )));
+        }
+        if end_time <= start_time {
+            return Err(YtcsError::ChapterError(format!(
+                "Chapter \"{}\" ends ({}) before it starts ({})",
+                title, end_time, start_time
+            )));
+        }
+        Ok(Self {
+            title,
+            start_time,
+            end_time,
+        })
+    }
+
     /// Calculates the chapter duration in seconds.
     ///
     /// # Returns
@@ -132,7 +160,7 @@ fn to_title_case(s: &str) -> String {
 /// Returns an error if:
 /// - JSON is malformed
 /// - The "chapters" field is missing
-/// - start_time or end_time fields are invalid
+/// - start_time or end_time fields are invalid, or a chapter ends before it starts
 pub fn parse_chapters_from_json(json_str: &str) -> Result<Vec<Chapter>> {
     let data: serde_json::Value = serde_json::from_str(json_str)?;
 
@@ -155,7 +183,7 @@ pub fn parse_chapters_from_json(json_str: &str) -> Result<Vec<Chapter>> {
             .as_f64()
             .ok_or_else(|| YtcsError::ChapterError("Invalid end_time".to_string()))?;
 
-        chapters.push(Chapter::new(title, start_time, end_time));
+        chapters.push(Chapter::try_new(title, start_time, end_time)?);
     }
 
     Ok(chapters)
@@ -2,15 +2,199 @@
 //!
 //! This module detects and parses timestamps in video descriptions
 //! to create chapters when YouTube metadata doesn't contain any.
+//!
+//! Several tracklist layouts are recognized (see [`TracklistFormat`]). Each
+//! one is tried on the whole description; the layout that reads the most
+//! lines — and whose times fit the video — wins.
 
 use crate::chapters::{Chapter, parse_timestamp};
 use crate::error::{Result, YtcsError};
+use once_cell::sync::Lazy;
 use regex::Regex;
 
+/// A timestamp such as `4:12`, `04:12` or `1:04:12`.
+const TS: &str = r"\d{1,2}:\d{2}(?::\d{2})?";
+
+/// `00:00 - 03:45 Title`
+static RE_RANGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^\[?({TS})\]?\s*[-–—~]\s*\[?{TS}\]?\s*[-–—:|]?\s*(.+?)$"
+    ))
+    .unwrap()
+});
+
+/// `[00:00:00] - Title`, `00:00 Title`
+static RE_LEADING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"^\[?({TS})\]?\s*[-–—:]?\s*(.+?)$")).unwrap());
+
+/// `Title - 3:45`, `01. Title 0:00`, `1 - Title (0:00)`
+static RE_TRAILING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"^(.+?)\s*[-–—:|]?\s*[(\[]?({TS})[)\]]?$")).unwrap());
+
+/// Track numbering in front of a title: `01.`, `1)`, `1 -`
+static RE_TRACK_NUMBER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\d{1,3}\s*(?:[.)]|[-–—])\s*").unwrap());
+
+/// A numbered line without a start time: `1. Title`, `01 - Title`, `A1 Title`
+static RE_UNTIMED_NUMBERED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(\d{1,3})|([A-Da-d])(\d{1,2}))(?:\s*[.):\-–—]\s*|\s+)(.+)$").unwrap()
+});
+
+/// A `Tracklist:` style header introducing an unnumbered list
+static RE_TRACKLIST_HEADER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:track\s*list(?:ing)?|tracks|set\s*list|songs)\s*:?$").unwrap()
+});
+
+/// A track length or start time at the end of an untimed title: `Song (4:12)`
+static RE_TRAILING_TIME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"\s*[-–—|]?\s*[(\[]?{TS}[)\]]?$")).unwrap());
+
+/// Untimed tracklists need at least this many titles.
+const MIN_UNTIMED_TRACKS: usize = 3;
+
+/// Tracklist layouts understood by [`parse_chapters_from_description`], in
+/// order of preference when two of them read the same lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TracklistFormat {
+    /// `00:00 - 03:45 Title`: a time range, the start is used
+    Range,
+    /// `00:00 Title` / `[00:00:00] - Title`: start time first
+    Leading,
+    /// `Title - 3:45`, `01. Title 0:00`, `1 - Title (0:00)`: start time last
+    Trailing,
+    /// `1. Song (4:12)`: each time is the track's length; starts are summed up
+    Durations,
+}
+
+impl TracklistFormat {
+    const ALL: [TracklistFormat; 4] = [
+        TracklistFormat::Range,
+        TracklistFormat::Leading,
+        TracklistFormat::Trailing,
+        TracklistFormat::Durations,
+    ];
+
+    /// Reads one trimmed line as `(time, title)`.
+    fn parse_line(self, line: &str) -> Option<(f64, String)> {
+        let (timestamp, title) = match self {
+            TracklistFormat::Range => {
+                let caps = RE_RANGE.captures(line)?;
+                (caps.get(1)?.as_str(), caps.get(2)?.as_str().to_string())
+            }
+            TracklistFormat::Leading => {
+                let caps = RE_LEADING.captures(line)?;
+                (caps.get(1)?.as_str(), caps.get(2)?.as_str().to_string())
+            }
+            TracklistFormat::Trailing | TracklistFormat::Durations => {
+                let caps = RE_TRAILING.captures(line)?;
+                let title = caps.get(1)?.as_str();
+                let title = RE_TRACK_NUMBER.replace(title, "");
+                let title = title.trim_end_matches(|c: char| {
+                    c.is_whitespace() || matches!(c, '-' | '–' | '—' | ':' | '|')
+                });
+                (caps.get(2)?.as_str(), title.to_string())
+            }
+        };
+        let title = title.trim();
+        // Ignore empty or too short titles
+        if title.len() < 2 {
+            return None;
+        }
+        // Keep the original title as-is: it's used for ID3/display tags.
+        // Filesystem sanitization happens separately, only when building the
+        // output filename (see Chapter::sanitize_title).
+        Some((parse_timestamp(timestamp).ok()?, title.to_string()))
+    }
+}
+
+/// One way of reading the description, and how well it fits.
+struct Candidate {
+    format: TracklistFormat,
+    /// `(start, title)` in description order
+    starts: Vec<(f64, String)>,
+    /// Lines read by this format
+    lines: usize,
+    /// Whether the times agree with the video length
+    fits: bool,
+}
+
+/// Reads every line of `description` with `format`.
+///
+/// Returns `None` when the times can't describe this video: start times past
+/// its end (or, for trailing times, not increasing), or track lengths whose
+/// total is far from `video_duration`.
+fn read_candidate(
+    format: TracklistFormat,
+    description: &str,
+    video_duration: f64,
+) -> Option<Candidate> {
+    let entries: Vec<(f64, String)> = description
+        .lines()
+        .filter_map(|line| format.parse_line(line.trim()))
+        .collect();
+    if entries.len() < 2 {
+        return None;
+    }
+    let lines = entries.len();
+
+    if format == TracklistFormat::Durations {
+        let total: f64 = entries.iter().map(|(d, _)| d).sum();
+        let tolerance = (video_duration * 0.05).max(30.0);
+        if (total - video_duration).abs() > tolerance || entries.iter().any(|(d, _)| *d <= 0.0) {
+            return None;
+        }
+        let mut start = 0.0;
+        let starts = entries
+            .into_iter()
+            .map(|(length, title)| {
+                let entry = (start, title);
+                start += length;
+                entry
+            })
+            .collect();
+        return Some(Candidate {
+            format,
+            starts,
+            lines,
+            fits: true,
+        });
+    }
+
+    // Check that timestamps are within video duration
+    let starts: Vec<(f64, String)> = entries
+        .into_iter()
+        .filter(|(start, _)| *start < video_duration)
+        .collect();
+    if starts.len() < 2 {
+        return None;
+    }
+    // A trailing time could be a length as well as a start; only increasing
+    // times are read as starts.
+    if format == TracklistFormat::Trailing && starts.windows(2).any(|w| w[1].0 <= w[0].0) {
+        return None;
+    }
+    // A tracklist of start times normally begins with the video.
+    let fits = starts[0].0 <= 5.0;
+    Some(Candidate {
+        format,
+        starts,
+        lines,
+        fits,
+    })
+}
+
 /// Parses chapters from a video description.
 ///
-/// Detects lines containing a timestamp followed by a title.
-/// Expected format: [HH:MM:SS] - Title or HH:MM:SS - Title
+/// Detects lines containing a timestamp and a title. Supported layouts:
+///
+/// - `[00:00:00] - Title`, `00:00 Title` (start time first)
+/// - `00:00 - 03:45 Title` (time range)
+/// - `Title - 3:45`, `01. Title 0:00`, `1 - Title (0:00)` (start time last)
+/// - `1. Song (4:12)` (track lengths; start times are accumulated)
+///
+/// Every layout is tried; the one reading the most lines wins, preferring
+/// start times that begin at the start of the video and track lengths that
+/// add up to `video_duration`.
 ///
 /// # Arguments
 ///
@@ -47,84 +231,28 @@ pub fn parse_chapters_from_description(
     log::debug!("Video duration: {:.2}s", video_duration);
     log::debug!("Description length: {} characters", description.len());
 
-    // Regex to detect: optional timestamp in brackets + separator + title
-    // Supported formats:
-    // [00:00:00] - Title
-    // [00:00] - Title
-    // 00:00:00 - Title
-    // 00:00 - Title
-    // 00:00:00 Title
-    // 1 - Title (0:00)
-    // 2 - Title (4:24)
-    let re = Regex::new(r"(?m)^\s*\[?(\d{1,2}:\d{2}(?::\d{2})?)\]?\s*[-–—:]?\s*(.+?)\s*$")
-        .map_err(|e| YtcsError::ChapterError(format!("Regex error: {}", e)))?;
-
-    // Regex for the format with track number at beginning: "1 - Title (0:00)"
-    let re_track_format =
-        Regex::new(r"(?m)^\s*(\d+)\s*[-–—]\s*(.+?)\s*\((\d{1,2}:\d{2}(?::\d{2})?)\)\s*$")
-            .map_err(|e| YtcsError::ChapterError(format!("Regex error: {}", e)))?;
-
-    let mut chapters_data: Vec<(f64, String)> = Vec::new();
-
-    // First try the format with track number: "1 - Title (0:00)"
-    for cap in re_track_format.captures_iter(description) {
-        if let (Some(_track_num_match), Some(title_match), Some(timestamp_match)) =
-            (cap.get(1), cap.get(2), cap.get(3))
-        {
-            let timestamp_str = timestamp_match.as_str();
-            let title = title_match.as_str().trim();
-
-            // Ignore empty or too short lines
-            if title.is_empty() || title.len() < 2 {
-                continue;
-            }
-
-            // Parse the timestamp
-            if let Ok(start_time) = parse_timestamp(timestamp_str) {
-                // Check that timestamp is within video duration
-                if start_time < video_duration {
-                    // Keep the original title as-is: it's used for ID3/display tags.
-                    // Filesystem sanitization happens separately, only when
-                    // building the output filename (see Chapter::sanitize_title).
-                    chapters_data.push((start_time, title.to_string()));
-                }
-            }
-        }
-    }
-
-    // If no chapter found with track format, try the classic format
-    if chapters_data.is_empty() {
-        for cap in re.captures_iter(description) {
-            if let (Some(timestamp_match), Some(title_match)) = (cap.get(1), cap.get(2)) {
-                let timestamp_str = timestamp_match.as_str();
-                let title = title_match.as_str().trim();
-
-                // Ignore empty or too short lines
-                if title.is_empty() || title.len() < 2 {
-                    continue;
-                }
-
-                // Parse the timestamp
-                if let Ok(start_time) = parse_timestamp(timestamp_str) {
-                    // Check that timestamp is within video duration
-                    if start_time < video_duration {
-                        // Keep the original title as-is (see comment above).
-                        chapters_data.push((start_time, title.to_string()));
-                    }
-                }
-            }
-        }
-    }
+    let best = TracklistFormat::ALL
+        .iter()
+        .filter_map(|format| read_candidate(*format, description, video_duration))
+        // max_by_key keeps the last maximum; reversing keeps the preferred format
+        .rev()
+        .max_by_key(|c| (c.lines, c.fits));
 
     // Check that we found at least 2 chapters
-    if chapters_data.len() < 2 {
+    let Some(best) = best else {
         return Err(YtcsError::ChapterError(
             "Not enough chapters found in description (need at least 2)".to_string(),
         ));
-    }
+    };
+    log::debug!(
+        "Description tracklist read as {:?} ({} lines)",
+        best.format,
+        best.lines
+    );
 
+    let mut chapters_data = best.starts;
     // Sort by timestamp
-    chapters_data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    chapters_data.sort_by(|a, b| a.0.total_cmp(&b.0));
 
     // Create chapters with end_time
     let mut chapters = Vec::new();
@@ -138,7 +266,7 @@ pub fn parse_chapters_from_description(
 
         // Check that chapter has a valid duration (at least 1 second)
         if end_time > *start_time + 1.0 {
-            chapters.push(Chapter::new(title.clone(), *start_time, end_time));
+            chapters.push(Chapter::try_new(title.clone(), *start_time, end_time)?);
         }
     }
 
@@ -156,6 +284,136 @@ pub fn parse_chapters_from_description(
     Ok(chapters)
 }
 
+/// Position of a numbered line: `(side, number)`, e.g. `(None, 3)` for `3.`
+/// or `(Some('B'), 1)` for `B1`.
+type TrackKey = (Option<char>, u32);
+
+fn read_numbered_line(line: &str) -> Option<(TrackKey, String)> {
+    let caps = RE_UNTIMED_NUMBERED.captures(line)?;
+    let key = match (caps.get(1), caps.get(2), caps.get(3)) {
+        (Some(n), _, _) => (None, n.as_str().parse().ok()?),
+        (None, Some(side), Some(n)) => (
+            side.as_str().chars().next()?.to_ascii_uppercase().into(),
+            n.as_str().parse().ok()?,
+        ),
+        _ => return None,
+    };
+    Some((key, caps.get(4)?.as_str().to_string()))
+}
+
+/// Whether `next` is the track right after `previous` (`2` after `1`, `A3`
+/// after `A2`, `B1` after any `A` track).
+fn follows(previous: TrackKey, next: TrackKey) -> bool {
+    match (previous, next) {
+        ((None, a), (None, b)) => b == a + 1,
+        ((Some(s), a), (Some(t), b)) => {
+            (t == s && b == a + 1) || (t as u32 == s as u32 + 1 && b == 1)
+        }
+        _ => false,
+    }
+}
+
+/// Cleans an untimed title; `None` for lines that can't be a title.
+fn untimed_title(title: &str) -> Option<String> {
+    if title.contains("http://") || title.contains("https://") || title.contains("www.") {
+        return None;
+    }
+    let title = RE_TRAILING_TIME.replace(title.trim(), "");
+    let title = title.trim();
+    (title.chars().count() >= 2).then(|| title.to_string())
+}
+
+/// Runs of lines numbered `1, 2, 3, …` (or `A1, A2, B1, …`); blank lines
+/// inside a run are allowed.
+fn numbered_runs(description: &str) -> Vec<Vec<String>> {
+    let mut runs = Vec::new();
+    let mut run: Vec<String> = Vec::new();
+    let mut last: Option<TrackKey> = None;
+    for line in description.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        let entry =
+            read_numbered_line(line).and_then(|(key, title)| Some((key, untimed_title(&title)?)));
+        match entry {
+            Some((key, title)) if last.is_some_and(|l| follows(l, key)) => {
+                run.push(title);
+                last = Some(key);
+            }
+            Some((key, title)) if key.1 == 1 => {
+                runs.push(std::mem::take(&mut run));
+                run.push(title);
+                last = Some(key);
+            }
+            _ => {
+                runs.push(std::mem::take(&mut run));
+                last = None;
+            }
+        }
+    }
+    runs.push(run);
+    runs
+}
+
+/// Lines following a `Tracklist:` header, up to the next blank line.
+fn headed_lists(description: &str) -> Vec<Vec<String>> {
+    let lines: Vec<&str> = description.lines().map(str::trim).collect();
+    let mut lists = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if !RE_TRACKLIST_HEADER.is_match(line) {
+            continue;
+        }
+        let list: Vec<String> = lines[i + 1..]
+            .iter()
+            .skip_while(|l| l.is_empty())
+            .take_while(|l| !l.is_empty())
+            .map(|l| {
+                let l = l.trim_start_matches(['-', '•', '*', '·']).trim_start();
+                RE_TRACK_NUMBER.replace(l, "").into_owned()
+            })
+            .map_while(|l| untimed_title(&l))
+            .collect();
+        lists.push(list);
+    }
+    lists
+}
+
+/// Parses a tracklist of titles without times from a video description.
+///
+/// Recognized are lines numbered from one (`1. Title`, `01 - Title`, `1)
+/// Title`, vinyl sides `A1 Title`, `B1 Title`) and unnumbered lines under a
+/// `Tracklist:` / `Tracks:` / `Setlist:` header, ending at a blank line. A
+/// trailing length such as `(4:12)` is dropped from the titles. The longest
+/// list wins; lists shorter than three titles are ignored.
+///
+/// Used with silence detection: the titles name the tracks found by
+/// [`crate::audio::detect_silence_chapters_for_count`].
+///
+/// # Examples
+///
+/// ```
+/// use youtube_chapter_splitter::chapters_from_description::parse_untimed_tracklist;
+///
+/// let description = "Tracklist:\n1. Intro\n2. The Song\n3. Outro\n\nFollow us!";
+/// assert_eq!(
+///     parse_untimed_tracklist(description).unwrap(),
+///     vec!["Intro", "The Song", "Outro"]
+/// );
+/// ```
+pub fn parse_untimed_tracklist(description: &str) -> Option<Vec<String>> {
+    let best = numbered_runs(description)
+        .into_iter()
+        .chain(headed_lists(description))
+        // max_by_key keeps the last maximum; reversing keeps the first list
+        .rev()
+        .max_by_key(Vec::len)?;
+    if best.len() < MIN_UNTIMED_TRACKS {
+        return None;
+    }
+    log::info!("Found {} untimed titles in description", best.len());
+    Some(best)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,150 @@
+//! Chapter timestamps from the comments of a video.
+//!
+//! When the description has no tracklist, one is often posted in a comment
+//! (usually pinned by the uploader). [`fetch_comments`] asks yt-dlp for the
+//! top comments, each comment is read with the description parser, and
+//! [`best_tracklist`] keeps the one that covers most of the video.
+
+use crate::chapters::Chapter;
+use crate::chapters_from_description::parse_chapters_from_description;
+use crate::error::{Result, YtcsError};
+use crate::ytdlp_error_parser;
+use serde::Deserialize;
+use std::process::Command;
+
+/// Comments with fewer timestamps are remarks ("2:31 and 14:02 🔥"), not tracklists.
+const MIN_TRACKS: usize = 3;
+
+/// Share of the video a comment's timestamps must span to be used.
+const MIN_COVERAGE: f64 = 0.5;
+
+/// A top-level comment as reported by yt-dlp.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Comment {
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub is_pinned: bool,
+    #[serde(default)]
+    pub author_is_uploader: bool,
+    #[serde(default)]
+    pub like_count: Option<u64>,
+    /// `"root"` for top-level comments, the parent's id for replies
+    #[serde(default)]
+    pub parent: Option<String>,
+}
+
+/// The tracklist chosen from the comments.
+#[derive(Debug, Clone)]
+pub struct CommentTracklist {
+    pub chapters: Vec<Chapter>,
+    pub author: Option<String>,
+    pub pinned: bool,
+    /// Share of the video duration covered by the timestamps (0–1)
+    pub coverage: f64,
+}
+
+/// Reads the `comments` array from yt-dlp's `--dump-json` output.
+///
+/// Replies are skipped; a video without comments yields an empty list.
+///
+/// # Errors
+///
+/// Returns a `JsonError` if the output isn't valid JSON
+pub fn parse_comments(json: &str) -> Result<Vec<Comment>> {
+    let data: serde_json::Value = serde_json::from_str(json)?;
+    let Some(raw) = data.get("comments").filter(|c| !c.is_null()) else {
+        return Ok(Vec::new());
+    };
+    let comments: Vec<Comment> = serde_json::from_value(raw.clone())?;
+    Ok(comments
+        .into_iter()
+        .filter(|c| c.parent.as_deref().is_none_or(|p| p == "root"))
+        .collect())
+}
+
+/// Fetches up to `max_comments` top comments of `url` (no replies).
+///
+/// # Errors
+///
+/// Returns a `DownloadError` if yt-dlp can't be run or fails
+pub fn fetch_comments(
+    url: &str,
+    cookies_from_browser: Option<&str>,
+    max_comments: usize,
+) -> Result<Vec<Comment>> {
+    let mut cmd = Command::new("yt-dlp");
+    cmd.arg("--dump-json")
+        .arg("--no-playlist")
+        .arg("--write-comments")
+        .arg("--extractor-args")
+        .arg(format!(
+            "youtube:max_comments={0},{0},0,0;comment_sort=top",
+            max_comments
+        ));
+    crate::ytdlp_helper::add_ejs_args(&mut cmd);
+    crate::cookie_helper::add_cookie_args(&mut cmd, cookies_from_browser);
+    cmd.arg(url);
+    log::debug!("Fetching up to {} comments of {}", max_comments, url);
+
+    let output = cmd
+        .output()
+        .map_err(|e| YtcsError::DownloadError(format!("Failed to execute yt-dlp: {}", e)))?;
+    if !output.status.success() {
+        let raw_error = String::from_utf8_lossy(&output.stderr);
+        let (error_msg, _) =
+            ytdlp_error_parser::parse_ytdlp_error(&raw_error, cookies_from_browser);
+        return Err(YtcsError::DownloadError(error_msg));
+    }
+    parse_comments(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Share of `duration` spanned by the chapter starts, counting the last
+/// chapter as long as an average one.
+fn coverage(chapters: &[Chapter], duration: f64) -> f64 {
+    let (Some(first), Some(last)) = (chapters.first(), chapters.last()) else {
+        return 0.0;
+    };
+    if duration <= 0.0 || chapters.len() < 2 {
+        return 0.0;
+    }
+    let span = last.start_time - first.start_time;
+    let average = span / (chapters.len() - 1) as f64;
+    ((span + average) / duration).min(1.0)
+}
+
+/// Picks the comment tracklist covering most of the video.
+///
+/// Comments with fewer than three timestamps, or spanning less than half of
+/// the video, are ignored. Equal coverage (to the percent) goes to a pinned or
+/// uploader comment, then to the longer tracklist, then to the more liked one.
+pub fn best_tracklist(comments: &[Comment], duration: f64) -> Option<CommentTracklist> {
+    comments
+        .iter()
+        .filter_map(|comment| {
+            let chapters = parse_chapters_from_description(&comment.text, duration).ok()?;
+            let coverage = coverage(&chapters, duration);
+            if chapters.len() < MIN_TRACKS || coverage < MIN_COVERAGE {
+                return None;
+            }
+            Some((comment, chapters, coverage))
+        })
+        // max_by_key keeps the last maximum; reversing keeps the higher comment
+        .rev()
+        .max_by_key(|(comment, chapters, coverage)| {
+            (
+                (coverage * 100.0).round() as u32,
+                comment.is_pinned || comment.author_is_uploader,
+                chapters.len(),
+                comment.like_count.unwrap_or(0),
+            )
+        })
+        .map(|(comment, chapters, coverage)| CommentTracklist {
+            chapters,
+            author: comment.author.clone(),
+            pinned: comment.is_pinned,
+            coverage,
+        })
+}
@@ -2,13 +2,24 @@
 //!
 //! This module handles application configuration stored in a TOML file.
 
+use crate::chapter_filter::ChapterFilter;
 use crate::error::{Result, YtcsError};
+use crate::playlist_selection::title_pattern;
+use crate::sponsorblock;
+use crate::title_rules::{CaseStyle, TitleRules};
 use crate::utils;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{self, Write};
 use std::path::PathBuf;
 
+/// `%y` with its brackets or one adjacent separator, removed when the release
+/// year is unknown.
+static RE_EMPTY_YEAR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\s*[\[(]%y[\])]|%y\s*-\s*|\s*-\s*%y|%y").unwrap());
+
 /// Output container / codec for yt-dlp extraction and per-chapter ffmpeg encoding.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -33,6 +44,75 @@ impl AudioFormat {
     }
 }
 
+/// What a run produces from one video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// One file per chapter (default)
+    #[default]
+    Tracks,
+    /// One file for the whole video with embedded chapter markers
+    SingleFile,
+}
+
+/// What happens to chapters left out by `chapter_include` / `chapter_exclude`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExcludedChapters {
+    /// Leave their audio out entirely (default)
+    #[default]
+    Drop,
+    /// Add their audio to the neighbouring track
+    Merge,
+}
+
+/// How `refine_chapters` moves chapter cuts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RefineStrategy {
+    /// Snap to the nearest silence (default)
+    #[default]
+    Silence,
+    /// Move to the quietest point, for crossfaded mixes without silences
+    Energy,
+}
+
+/// What to do with SponsorBlock segments (sponsor reads, "subscribe" reminders, …).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SponsorBlockMode {
+    /// Don't look segments up (default)
+    #[default]
+    Off,
+    /// Cut the segments out of the audio; chapter times move up accordingly
+    Remove,
+    /// Make each segment a chapter of its own (`[Sponsor]`, …)
+    Mark,
+}
+
+/// Container for `OutputMode::SingleFile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChapteredFormat {
+    /// MPEG-4 audiobook (AAC)
+    #[default]
+    M4b,
+    /// Matroska audio (keeps the downloaded codec as-is)
+    Mka,
+    /// Ogg Opus
+    Opus,
+}
+
+impl ChapteredFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ChapteredFormat::M4b => "m4b",
+            ChapteredFormat::Mka => "mka",
+            ChapteredFormat::Opus => "opus",
+        }
+    }
+}
+
 /// Playlist detection behavior
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -86,6 +166,10 @@ pub struct Config {
     #[serde(default = "default_directory_format")]
     pub directory_format: String,
 
+    /// Use the uploader's channel name as artist when the title has none
+    #[serde(default = "default_channel_as_artist")]
+    pub channel_as_artist: bool,
+
     /// Audio quality in kbps (128, 192, or 320)
     #[serde(default = "default_audio_quality")]
     pub audio_quality: u32,
@@ -106,10 +190,33 @@ pub struct Config {
     #[serde(default)]
     pub create_playlist: bool,
 
+    /// Keep the full-length download next to the split tracks (tagged, with chapter markers)
+    #[serde(default)]
+    pub keep_source_audio: bool,
+
+    /// File name format for the kept full-length file (see `keep_source_audio`)
+    /// Available placeholders:
+    /// - %a: artist
+    /// - %A: album
+    #[serde(default = "default_source_audio_format")]
+    pub source_audio_format: String,
+
+    /// Split into tracks, or write a single chaptered file
+    #[serde(default)]
+    pub output_mode: OutputMode,
+
+    /// Container used when `output_mode = "single_file"`
+    #[serde(default)]
+    pub single_file_format: ChapteredFormat,
+
     /// Adjust chapter boundaries using silence detection (extra ffmpeg pass)
     #[serde(default = "default_refine_chapters")]
     pub refine_chapters: bool,
 
+    /// Where refined cuts go: nearest silence, or quietest point (crossfaded mixes)
+    #[serde(default)]
+    pub refine_strategy: RefineStrategy,
+
     /// Search window (seconds, ±) for silence refinement around chapter edges
     #[serde(default = "default_refine_silence_window")]
     pub refine_silence_window: f64,
@@ -122,10 +229,88 @@ pub struct Config {
     #[serde(default = "default_refine_min_silence")]
     pub refine_min_silence: f64,
 
+    /// Refined cuts may move at most this far (seconds) from the declared time (0 = window only)
+    #[serde(default)]
+    pub refine_max_delta: f64,
+
+    /// Refined cuts below this confidence (0–1) keep the declared time (0 = off)
+    #[serde(default)]
+    pub refine_min_confidence: f64,
+
+    /// Record every refined cut (moves, confidence, rejections) in ytcs-refinement.json
+    #[serde(default = "default_refine_manifest")]
+    pub refine_manifest: bool,
+
+    /// Noise threshold in dB for finding tracks by silence (no chapters or timestamps)
+    #[serde(default = "default_silence_noise_db")]
+    pub silence_noise_db: f64,
+
+    /// Minimum silence duration (seconds) between tracks found by silence
+    #[serde(default = "default_silence_min_duration")]
+    pub silence_min_duration: f64,
+
+    /// Tracks found by silence shorter than this (seconds) join a neighbour (0 = off)
+    #[serde(default = "default_min_track_duration")]
+    pub min_track_duration: f64,
+
+    /// Silence detection finding more tracks than this is an error
+    #[serde(default = "default_max_tracks")]
+    pub max_tracks: usize,
+
+    /// Look for a tracklist in the top comments when the description has none
+    #[serde(default)]
+    pub comment_tracklists: bool,
+
+    /// How many top comments to fetch when looking for a tracklist
+    #[serde(default = "default_max_comments")]
+    pub max_comments: usize,
+
+    /// Show the chapter plan and allow editing it before splitting (interactive runs only)
+    #[serde(default)]
+    pub review_chapters: bool,
+
+    /// Only chapters whose title matches this regex become tracks (case-insensitive)
+    #[serde(default)]
+    pub chapter_include: Option<String>,
+
+    /// Chapters whose title matches this regex don't become tracks (case-insensitive)
+    #[serde(default)]
+    pub chapter_exclude: Option<String>,
+
+    /// Drop the audio of left-out chapters, or merge it into the neighbouring track
+    #[serde(default)]
+    pub excluded_chapters: ExcludedChapters,
+
+    /// Look up SponsorBlock segments of YouTube videos and remove or mark them
+    #[serde(default)]
+    pub sponsorblock: SponsorBlockMode,
+
+    /// SponsorBlock API server (e.g. a local mirror)
+    #[serde(default = "default_sponsorblock_api")]
+    pub sponsorblock_api: String,
+
+    /// SponsorBlock categories to act on (`sponsor`, `selfpromo`, `interaction`,
+    /// `intro`, `outro`, `preview`, `music_offtopic`, `filler`)
+    #[serde(default = "sponsorblock::default_categories")]
+    pub sponsorblock_categories: Vec<String>,
+
     /// Prefix album folder with `01-`, `02-`, … when processing multiple playlist entries
     #[serde(default)]
     pub playlist_prefix_index: bool,
 
+    /// Treat YouTube Music album playlists (`OLAK5uy_…`) as one album, one video
+    /// per track. Off by default so `playlist_behavior` decides as for any playlist.
+    #[serde(default = "default_album_playlists")]
+    pub album_playlists: bool,
+
+    /// Playlist entries downloaded at the same time (1 = one after another)
+    #[serde(default = "default_parallel_downloads")]
+    pub parallel_downloads: usize,
+
+    /// Playlist entries analyzed/split by ffmpeg at the same time
+    #[serde(default = "default_parallel_splits")]
+    pub parallel_splits: usize,
+
     /// Playlist detection behavior (default: VideoOnly for v1.0)
     #[serde(default)]
     pub playlist_behavior: PlaylistBehavior,
@@ -151,6 +336,10 @@ pub struct Config {
     /// Minimum days between auto-update attempts (0 = always check)
     #[serde(default = "default_ytdlp_update_interval")]
     pub ytdlp_update_interval_days: u64,
+
+    /// Extra cleanup for artist, album and track titles (`[title_rules]`)
+    #[serde(default, skip_serializing_if = "TitleRules::is_empty")]
+    pub title_rules: TitleRules,
 }
 
 // Default value functions for serde
@@ -166,6 +355,14 @@ fn default_directory_format() -> String {
     "%a - %A".to_string()
 }
 
+fn default_channel_as_artist() -> bool {
+    true
+}
+
+fn default_source_audio_format() -> String {
+    "%a - %A (full)".to_string()
+}
+
 fn default_audio_quality() -> u32 {
     192
 }
@@ -182,10 +379,50 @@ fn default_refine_chapters() -> bool {
     true
 }
 
+fn default_refine_manifest() -> bool {
+    true
+}
+
+fn default_album_playlists() -> bool {
+    false
+}
+
 fn default_refine_min_silence() -> f64 {
     1.2
 }
 
+fn default_silence_noise_db() -> f64 {
+    -30.0
+}
+
+fn default_silence_min_duration() -> f64 {
+    2.0
+}
+
+fn default_min_track_duration() -> f64 {
+    10.0
+}
+
+fn default_max_tracks() -> usize {
+    100
+}
+
+fn default_max_comments() -> usize {
+    20
+}
+
+fn default_sponsorblock_api() -> String {
+    sponsorblock::DEFAULT_API.to_string()
+}
+
+fn default_parallel_downloads() -> usize {
+    1
+}
+
+fn default_parallel_splits() -> usize {
+    2
+}
+
 fn default_max_retries() -> u32 {
     3
 }
@@ -213,22 +450,48 @@ impl Default for Config {
             download_cover: true,
             filename_format: "%n - %t".to_string(),
             directory_format: "%a - %A".to_string(),
+            channel_as_artist: true,
             audio_quality: 192,
             audio_format: AudioFormat::Mp3,
             overwrite_existing: false,
             max_retries: 3,
             create_playlist: false,
+            keep_source_audio: false,
+            source_audio_format: "%a - %A (full)".to_string(),
+            output_mode: OutputMode::Tracks,
+            single_file_format: ChapteredFormat::M4b,
             refine_chapters: true,
+            refine_strategy: RefineStrategy::Silence,
             refine_silence_window: 5.0,
             refine_noise_db: -35.0,
             refine_min_silence: 1.2,
+            refine_max_delta: 0.0,
+            refine_min_confidence: 0.0,
+            refine_manifest: true,
+            silence_noise_db: -30.0,
+            silence_min_duration: 2.0,
+            min_track_duration: 10.0,
+            max_tracks: 100,
+            comment_tracklists: false,
+            max_comments: 20,
+            review_chapters: false,
+            chapter_include: None,
+            chapter_exclude: None,
+            excluded_chapters: ExcludedChapters::Drop,
+            sponsorblock: SponsorBlockMode::Off,
+            sponsorblock_api: sponsorblock::DEFAULT_API.to_string(),
+            sponsorblock_categories: sponsorblock::default_categories(),
             playlist_prefix_index: false,
+            album_playlists: false,
+            parallel_downloads: 1,
+            parallel_splits: 2,
             playlist_behavior: PlaylistBehavior::VideoOnly, // Changed from Ask for v1.0
             cookies_from_browser: None,
             download_timeout: 300,
             dependency_auto_install: AutoInstallBehavior::Prompt,
             ytdlp_auto_update: true,
             ytdlp_update_interval_days: 1,
+            title_rules: TitleRules::default(),
         }
     }
 }
@@ -291,13 +554,73 @@ impl Config {
                 "refine_min_silence must be a positive number of seconds".to_string(),
             ));
         }
+        if !self.refine_max_delta.is_finite() || self.refine_max_delta < 0.0 {
+            return Err(YtcsError::ConfigError(
+                "refine_max_delta must be a number of seconds (0 = no limit)".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.refine_min_confidence) {
+            return Err(YtcsError::ConfigError(
+                "refine_min_confidence must be between 0 and 1".to_string(),
+            ));
+        }
         if !self.refine_noise_db.is_finite() {
             return Err(YtcsError::ConfigError(
                 "refine_noise_db must be a finite number".to_string(),
             ));
         }
+        if !self.silence_noise_db.is_finite() || self.silence_noise_db >= 0.0 {
+            return Err(YtcsError::ConfigError(
+                "silence_noise_db must be a negative number of dB".to_string(),
+            ));
+        }
+        if !self.silence_min_duration.is_finite() || self.silence_min_duration <= 0.0 {
+            return Err(YtcsError::ConfigError(
+                "silence_min_duration must be a positive number of seconds".to_string(),
+            ));
+        }
+        if !self.min_track_duration.is_finite() || self.min_track_duration < 0.0 {
+            return Err(YtcsError::ConfigError(
+                "min_track_duration must be 0 or a positive number of seconds".to_string(),
+            ));
+        }
+        if self.max_tracks == 0 {
+            return Err(YtcsError::ConfigError(
+                "max_tracks must be at least 1".to_string(),
+            ));
+        }
+        if self.max_comments == 0 {
+            return Err(YtcsError::ConfigError(
+                "max_comments must be at least 1".to_string(),
+            ));
+        }
+        if self.parallel_downloads == 0 || self.parallel_splits == 0 {
+            return Err(YtcsError::ConfigError(
+                "parallel_downloads and parallel_splits must be at least 1".to_string(),
+            ));
+        }
         Self::validate_template("filename_format", &self.filename_format)?;
         Self::validate_template("directory_format", &self.directory_format)?;
+        Self::validate_template("source_audio_format", &self.source_audio_format)?;
+        if !(self.sponsorblock_api.starts_with("http://")
+            || self.sponsorblock_api.starts_with("https://"))
+        {
+            return Err(YtcsError::ConfigError(format!(
+                "sponsorblock_api must be an http(s) URL, got {:?}",
+                self.sponsorblock_api
+            )));
+        }
+        if self.sponsorblock_categories.is_empty() {
+            return Err(YtcsError::ConfigError(
+                "sponsorblock_categories must list at least one category".to_string(),
+            ));
+        }
+        ChapterFilter::new(
+            self.chapter_include.as_deref(),
+            self.chapter_exclude.as_deref(),
+            self.excluded_chapters,
+        )?;
+        self.title_rules.compile()?;
         Ok(())
     }
 
@@ -381,9 +704,35 @@ impl Config {
 
     /// Format directory name according to template
     pub fn format_directory(&self, artist: &str, album: &str) -> String {
+        self.format_directory_with_year(artist, album, None)
+    }
+
+    /// Format directory name according to template, filling `%y` with the
+    /// release year. Without a year, `%y` is dropped together with its
+    /// brackets or one adjacent ` - ` separator (`%a - %y - %A` → `%a - %A`).
+    pub fn format_directory_with_year(
+        &self,
+        artist: &str,
+        album: &str,
+        year: Option<u32>,
+    ) -> String {
+        let safe_a = utils::sanitize_filesystem_chars(artist);
+        let safe_al = utils::sanitize_filesystem_chars(album);
+        let template = match year {
+            Some(y) => self.directory_format.replace("%y", &y.to_string()),
+            None => RE_EMPTY_YEAR
+                .replace_all(&self.directory_format, "")
+                .trim()
+                .to_string(),
+        };
+        template.replace("%a", &safe_a).replace("%A", &safe_al)
+    }
+
+    /// Format the file name (without extension) of the kept full-length audio
+    pub fn format_source_audio_name(&self, artist: &str, album: &str) -> String {
         let safe_a = utils::sanitize_filesystem_chars(artist);
         let safe_al = utils::sanitize_filesystem_chars(album);
-        self.directory_format
+        self.source_audio_format
             .replace("%a", &safe_a)
             .replace("%A", &safe_al)
     }
@@ -413,6 +762,10 @@ pub fn print_config_summary() -> Result<()> {
         "  directory_format            = \"{}\"",
         config.directory_format
     );
+    println!(
+        "  channel_as_artist           = {}",
+        config.channel_as_artist
+    );
     println!(
         "  audio_quality               = {} kbps",
         config.audio_quality
@@ -424,7 +777,24 @@ pub fn print_config_summary() -> Result<()> {
     );
     println!("  max_retries                 = {}", config.max_retries);
     println!("  create_playlist             = {}", config.create_playlist);
+    println!(
+        "  keep_source_audio           = {}",
+        config.keep_source_audio
+    );
+    println!(
+        "  source_audio_format         = \"{}\"",
+        config.source_audio_format
+    );
+    println!("  output_mode                 = {:?}", config.output_mode);
+    println!(
+        "  single_file_format          = {:?}",
+        config.single_file_format
+    );
     println!("  refine_chapters             = {}", config.refine_chapters);
+    println!(
+        "  refine_strategy             = {:?}",
+        config.refine_strategy
+    );
     println!(
         "  refine_silence_window       = {} s",
         config.refine_silence_window
@@ -434,10 +804,65 @@ pub fn print_config_summary() -> Result<()> {
         "  refine_min_silence          = {} s",
         config.refine_min_silence
     );
+    println!(
+        "  refine_max_delta            = {} s",
+        config.refine_max_delta
+    );
+    println!(
+        "  refine_min_confidence       = {}",
+        config.refine_min_confidence
+    );
+    println!("  refine_manifest             = {}", config.refine_manifest);
+    println!(
+        "  silence_noise_db            = {}",
+        config.silence_noise_db
+    );
+    println!(
+        "  silence_min_duration        = {} s",
+        config.silence_min_duration
+    );
+    println!(
+        "  min_track_duration          = {} s",
+        config.min_track_duration
+    );
+    println!("  max_tracks                  = {}", config.max_tracks);
+    println!(
+        "  comment_tracklists          = {}",
+        config.comment_tracklists
+    );
+    println!("  max_comments                = {}", config.max_comments);
+    println!("  review_chapters             = {}", config.review_chapters);
+    println!(
+        "  chapter_include             = {:?}",
+        config.chapter_include.as_deref().unwrap_or("(none)")
+    );
+    println!(
+        "  chapter_exclude             = {:?}",
+        config.chapter_exclude.as_deref().unwrap_or("(none)")
+    );
+    println!(
+        "  excluded_chapters           = {:?}",
+        config.excluded_chapters
+    );
+    println!("  sponsorblock                = {:?}", config.sponsorblock);
+    println!(
+        "  sponsorblock_api            = {}",
+        config.sponsorblock_api
+    );
+    println!(
+        "  sponsorblock_categories     = {}",
+        config.sponsorblock_categories.join(", ")
+    );
     println!(
         "  playlist_prefix_index       = {}",
         config.playlist_prefix_index
     );
+    println!("  album_playlists             = {}", config.album_playlists);
+    println!(
+        "  parallel_downloads          = {}",
+        config.parallel_downloads
+    );
+    println!("  parallel_splits             = {}", config.parallel_splits);
     println!(
         "  playlist_behavior           = {:?}",
         config.playlist_behavior
@@ -462,8 +887,19 @@ pub fn print_config_summary() -> Result<()> {
         "  ytdlp_update_interval_days  = {}",
         config.ytdlp_update_interval_days
     );
+    let rules = &config.title_rules;
+    println!(
+        "  title_rules                 = {} replace, {} strip, case {}, {} small words",
+        rules.replace.len(),
+        rules.strip.len(),
+        rules
+            .case
+            .map_or("built-in".to_string(), |c| format!("{:?}", c)
+                .to_lowercase()),
+        rules.small_words.len()
+    );
     println!();
-    println!("Placeholders: filename %n %t %a %A — directory %a %A");
+    println!("Placeholders: filename %n %t %a %A — directory %a %A %y — full-length file %a %A");
 
     Ok(())
 }
@@ -560,13 +996,52 @@ pub fn run_interactive_config_wizard() -> Result<()> {
     let df = config.directory_format.clone();
     let input = prompt_line(
         "Album folder name format",
-        "Placeholders: %a artist, %A album.",
+        "Placeholders: %a artist, %A album, %y release year (dropped when unknown).",
         &df,
     );
     if !input.is_empty() {
         config.directory_format = input;
     }
 
+    let ca = config.channel_as_artist;
+    let input = prompt_line(
+        "Channel name as artist fallback",
+        "y/n — when the title has no \"Artist - Album\", use the uploader's channel (minus \" - Topic\", \"VEVO\", \"Official\").",
+        &format!("{}", ca),
+    );
+    config.channel_as_artist = parse_bool_input(&input, ca)?;
+
+    println!("Title case style (artist, album, track titles)");
+    println!("  1 = built-in: title case names, track tags as published  2 = keep");
+    println!("  3 = title  4 = sentence  5 = lower");
+    println!(
+        "  Replace/strip rules and small words: [title_rules] in config.toml; preview with `ytcs test-title`."
+    );
+    print!(
+        "  [default: {}] > ",
+        config
+            .title_rules
+            .case
+            .map_or("built-in".to_string(), |c| format!("{:?}", c)
+                .to_lowercase())
+    );
+    io::stdout().flush().ok();
+    let case_in = read_line_trimmed();
+    if !case_in.is_empty() {
+        config.title_rules.case = match case_in.as_str() {
+            "1" => None,
+            "2" => Some(CaseStyle::Keep),
+            "3" => Some(CaseStyle::Title),
+            "4" => Some(CaseStyle::Sentence),
+            "5" => Some(CaseStyle::Lower),
+            _ => {
+                return Err(YtcsError::ConfigError(
+                    "Enter 1 to 5 (or leave empty to keep)".to_string(),
+                ));
+            }
+        };
+    }
+
     let aq = config.audio_quality;
     let input = prompt_line(
         "MP3 bitrate (kbps)",
@@ -621,6 +1096,63 @@ pub fn run_interactive_config_wizard() -> Result<()> {
     );
     config.create_playlist = parse_bool_input(&input, cp)?;
 
+    let ksa = config.keep_source_audio;
+    let input = prompt_line(
+        "Keep the full-length audio",
+        "y/n — keep the original download as one tagged file with chapter markers.",
+        &format!("{}", ksa),
+    );
+    config.keep_source_audio = parse_bool_input(&input, ksa)?;
+
+    if config.keep_source_audio {
+        let saf = config.source_audio_format.clone();
+        let input = prompt_line(
+            "Full-length file name format",
+            "Placeholders: %a artist, %A album.",
+            &saf,
+        );
+        if !input.is_empty() {
+            config.source_audio_format = input;
+        }
+    }
+
+    println!("Output mode");
+    println!("  1 = one file per chapter (default)  2 = single file with chapter markers");
+    print!("  [default: {:?}] > ", config.output_mode);
+    io::stdout().flush().ok();
+    let om_in = read_line_trimmed();
+    if !om_in.is_empty() {
+        config.output_mode = match om_in.as_str() {
+            "1" => OutputMode::Tracks,
+            "2" => OutputMode::SingleFile,
+            _ => {
+                return Err(YtcsError::ConfigError(
+                    "Enter 1 or 2 (or leave empty to keep)".to_string(),
+                ));
+            }
+        };
+    }
+
+    if config.output_mode == OutputMode::SingleFile {
+        println!("Single file format");
+        println!("  1 = m4b (default)  2 = mka  3 = opus");
+        print!("  [default: {:?}] > ", config.single_file_format);
+        io::stdout().flush().ok();
+        let sf_in = read_line_trimmed();
+        if !sf_in.is_empty() {
+            config.single_file_format = match sf_in.as_str() {
+                "1" => ChapteredFormat::M4b,
+                "2" => ChapteredFormat::Mka,
+                "3" => ChapteredFormat::Opus,
+                _ => {
+                    return Err(YtcsError::ConfigError(
+                        "Enter 1, 2, or 3 (or leave empty to keep)".to_string(),
+                    ));
+                }
+            };
+        }
+    }
+
     let rc = config.refine_chapters;
     let input = prompt_line(
         "Refine chapter boundaries with silence detection",
@@ -629,6 +1161,25 @@ pub fn run_interactive_config_wizard() -> Result<()> {
     );
     config.refine_chapters = parse_bool_input(&input, rc)?;
 
+    println!("Refinement strategy");
+    println!(
+        "  1 = silence (default: nearest quiet gap)  2 = energy (quietest point; for crossfaded mixes)"
+    );
+    print!("  [default: {:?}] > ", config.refine_strategy);
+    io::stdout().flush().ok();
+    let rs_in = read_line_trimmed();
+    if !rs_in.is_empty() {
+        config.refine_strategy = match rs_in.as_str() {
+            "1" => RefineStrategy::Silence,
+            "2" => RefineStrategy::Energy,
+            _ => {
+                return Err(YtcsError::ConfigError(
+                    "Enter 1 or 2 (or leave empty to keep)".to_string(),
+                ));
+            }
+        };
+    }
+
     let rw = config.refine_silence_window;
     let input = prompt_line(
         "Refine silence search window (seconds)",
@@ -665,6 +1216,175 @@ pub fn run_interactive_config_wizard() -> Result<()> {
         })?;
     }
 
+    let rmd = config.refine_max_delta;
+    let input = prompt_line(
+        "Refine maximum move (seconds)",
+        "Refined cuts moving further than this keep the declared time (0 = only the window limits it).",
+        &format!("{}", rmd),
+    );
+    if !input.is_empty() {
+        config.refine_max_delta = input
+            .parse()
+            .map_err(|_| YtcsError::ConfigError("refine_max_delta must be a number".to_string()))?;
+    }
+
+    let rmc = config.refine_min_confidence;
+    let input = prompt_line(
+        "Refine minimum confidence (0-1)",
+        "Refined cuts scored lower (far from the declared time, short or shallow gap) keep the declared time (0 = off).",
+        &format!("{}", rmc),
+    );
+    if !input.is_empty() {
+        config.refine_min_confidence = input.parse().map_err(|_| {
+            YtcsError::ConfigError("refine_min_confidence must be a number".to_string())
+        })?;
+    }
+
+    let rm = config.refine_manifest;
+    let input = prompt_line(
+        "Write refinement manifest",
+        "y/n — save each refined cut (move, confidence, rejection) to ytcs-refinement.json in the album folder.",
+        &format!("{}", rm),
+    );
+    config.refine_manifest = parse_bool_input(&input, rm)?;
+
+    let snd = config.silence_noise_db;
+    let input = prompt_line(
+        "Track detection noise threshold (dB)",
+        "Used to find tracks by silence when a video has no chapters or timestamps (more negative = stricter).",
+        &format!("{}", snd),
+    );
+    if !input.is_empty() {
+        config.silence_noise_db = input
+            .parse()
+            .map_err(|_| YtcsError::ConfigError("silence_noise_db must be a number".to_string()))?;
+    }
+
+    let smd = config.silence_min_duration;
+    let input = prompt_line(
+        "Track detection minimum silence (seconds)",
+        "Shortest gap between two tracks found by silence.",
+        &format!("{}", smd),
+    );
+    if !input.is_empty() {
+        config.silence_min_duration = input.parse().map_err(|_| {
+            YtcsError::ConfigError("silence_min_duration must be a number".to_string())
+        })?;
+    }
+
+    let mtd = config.min_track_duration;
+    let input = prompt_line(
+        "Minimum track length (seconds)",
+        "Shorter pieces found by silence (quiet passages) join a neighbouring track; 0 keeps them.",
+        &format!("{}", mtd),
+    );
+    if !input.is_empty() {
+        config.min_track_duration = input.parse().map_err(|_| {
+            YtcsError::ConfigError("min_track_duration must be a number".to_string())
+        })?;
+    }
+
+    let mt = config.max_tracks;
+    let input = prompt_line(
+        "Maximum number of tracks",
+        "Silence detection finding more tracks than this stops instead of splitting.",
+        &format!("{}", mt),
+    );
+    if !input.is_empty() {
+        config.max_tracks = input
+            .parse()
+            .map_err(|_| YtcsError::ConfigError("max_tracks must be a whole number".to_string()))?;
+    }
+
+    let ct = config.comment_tracklists;
+    let input = prompt_line(
+        "Look for a tracklist in comments",
+        "y/n — when the description has no timestamps, read the top comments before falling back to silence detection.",
+        &format!("{}", ct),
+    );
+    config.comment_tracklists = parse_bool_input(&input, ct)?;
+
+    if config.comment_tracklists {
+        let mc = config.max_comments;
+        let input = prompt_line(
+            "Comments to fetch",
+            "Top comments read when looking for a tracklist (more = slower).",
+            &format!("{}", mc),
+        );
+        if !input.is_empty() {
+            config.max_comments = input.parse().map_err(|_| {
+                YtcsError::ConfigError("max_comments must be a whole number".to_string())
+            })?;
+        }
+    }
+
+    let rvc = config.review_chapters;
+    let input = prompt_line(
+        "Review chapters before splitting",
+        "y/n — show the chapter plan and rename, merge, split, delete or move cuts first.",
+        &format!("{}", rvc),
+    );
+    config.review_chapters = parse_bool_input(&input, rvc)?;
+
+    let ce_disp = config
+        .chapter_exclude
+        .clone()
+        .unwrap_or_else(|| "(none)".to_string());
+    let input = prompt_line(
+        "Leave out chapters matching",
+        "Regex on chapter titles, e.g. ^(intro|outro|sponsor) — or 'none' to keep every chapter.",
+        &ce_disp,
+    );
+    if !input.is_empty() {
+        if input.eq_ignore_ascii_case("none") || input == "-" {
+            config.chapter_exclude = None;
+        } else {
+            title_pattern(&input)?;
+            config.chapter_exclude = Some(input);
+        }
+    }
+
+    if config.chapter_exclude.is_some() || config.chapter_include.is_some() {
+        println!("Left-out chapters");
+        println!("  1 = drop their audio (default)  2 = merge into the neighbouring track");
+        print!("  [default: {:?}] > ", config.excluded_chapters);
+        io::stdout().flush().ok();
+        let ec_in = read_line_trimmed();
+        if !ec_in.is_empty() {
+            config.excluded_chapters = match ec_in.as_str() {
+                "1" => ExcludedChapters::Drop,
+                "2" => ExcludedChapters::Merge,
+                _ => {
+                    return Err(YtcsError::ConfigError(
+                        "Enter 1 or 2 (or leave empty to keep)".to_string(),
+                    ));
+                }
+            };
+        }
+    }
+
+    println!(
+        "SponsorBlock segments (YouTube only; server and categories: sponsorblock_api / sponsorblock_categories)"
+    );
+    println!(
+        "  1 = ignore (default)  2 = cut them out of the audio  3 = make them separate chapters"
+    );
+    print!("  [default: {:?}] > ", config.sponsorblock);
+    io::stdout().flush().ok();
+    let sb_in = read_line_trimmed();
+    if !sb_in.is_empty() {
+        config.sponsorblock = match sb_in.as_str() {
+            "1" => SponsorBlockMode::Off,
+            "2" => SponsorBlockMode::Remove,
+            "3" => SponsorBlockMode::Mark,
+            _ => {
+                return Err(YtcsError::ConfigError(
+                    "Enter 1, 2, or 3 (or leave empty to keep)".to_string(),
+                ));
+            }
+        };
+    }
+
     println!("Playlist behavior when a playlist URL is used");
     println!("  1 = ask  2 = video_only (default)  3 = playlist_only");
     print!("  [default: {:?}] > ", config.playlist_behavior);
@@ -691,6 +1411,38 @@ pub fn run_interactive_config_wizard() -> Result<()> {
     );
     config.playlist_prefix_index = parse_bool_input(&input, ppi)?;
 
+    let ap = config.album_playlists;
+    let input = prompt_line(
+        "Treat YouTube Music album playlists as one album",
+        "y/n — each video of an OLAK5uy_… playlist becomes a numbered track of one folder.",
+        &format!("{}", ap),
+    );
+    config.album_playlists = parse_bool_input(&input, ap)?;
+
+    let pd = config.parallel_downloads;
+    let input = prompt_line(
+        "Parallel playlist downloads",
+        "How many playlist entries to download at the same time (1 = one after another).",
+        &format!("{}", pd),
+    );
+    if !input.is_empty() {
+        config.parallel_downloads = input.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+            YtcsError::ConfigError("parallel_downloads must be at least 1".to_string())
+        })?;
+    }
+
+    let ps = config.parallel_splits;
+    let input = prompt_line(
+        "Parallel splits",
+        "How many downloaded entries ffmpeg may split at the same time.",
+        &format!("{}", ps),
+    );
+    if !input.is_empty() {
+        config.parallel_splits = input.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+            YtcsError::ConfigError("parallel_splits must be at least 1".to_string())
+        })?;
+    }
+
     let cb_disp = config
         .cookies_from_browser
         .clone()
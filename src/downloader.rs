@@ -1,22 +1,27 @@
-//! YouTube video download and metadata extraction.
+//! Video download and metadata extraction.
 //!
 //! This module handles interaction with `yt-dlp` to download videos
-//! and extract their metadata (title, duration, chapters).
+//! and extract their metadata (title, duration, chapters). YouTube is the
+//! main target, but any site yt-dlp supports goes through the same path
+//! (see [`crate::media_url`]).
 
 use crate::chapters::{Chapter, parse_chapters_from_json};
 use crate::error::{MissingToolsError, Result, YtcsError};
+use crate::media_url::MediaId;
 use crate::ytdlp_error_parser;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-/// Information about a YouTube video.
+/// Information about a video.
 #[derive(Debug)]
 pub struct VideoInfo {
     pub title: String,
     pub duration: f64,
     pub chapters: Vec<Chapter>,
-    pub video_id: String,
+    /// Site (yt-dlp extractor) and id of the video; the id is empty and the
+    /// extractor `generic` when yt-dlp reported no id.
+    pub media: MediaId,
     /// Video description from yt-dlp (for chapter timestamps when JSON chapters are empty).
     pub description: Option<String>,
     /// Upload date from yt-dlp (`upload_date`, often YYYYMMDD).
@@ -27,6 +32,88 @@ pub struct VideoInfo {
     pub webpage_url: Option<String>,
     /// Best thumbnail URL from yt-dlp (`thumbnail` field), when present.
     pub thumbnail: Option<String>,
+    /// Channel (or uploader) name, e.g. `Pink Floyd - Topic` or `PinkFloydVEVO`.
+    pub uploader: Option<String>,
+    /// Music metadata yt-dlp reports for YouTube Music / "Topic" uploads
+    /// (`artists` / `artist`, `album`, `track`, `release_year`).
+    pub music: MusicMetadata,
+}
+
+/// Artist/album/track fields yt-dlp exposes for catalogue music, mostly for
+/// uploads from auto-generated YouTube Music "Topic" channels.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MusicMetadata {
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track: Option<String>,
+    pub release_year: Option<u32>,
+}
+
+impl MusicMetadata {
+    fn from_ytdlp_json(data: &serde_json::Value) -> Self {
+        let text = |key: &str| {
+            data[key]
+                .as_str()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let artists: Vec<&str> = data["artists"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let artist = if artists.is_empty() {
+            text("artist")
+        } else {
+            Some(artists.join(", "))
+        };
+        let release_year = data["release_year"]
+            .as_u64()
+            .and_then(|y| u32::try_from(y).ok())
+            .or_else(|| {
+                data["release_date"]
+                    .as_str()
+                    .and_then(|d| d.get(..4))
+                    .and_then(|y| y.parse().ok())
+            });
+        Self {
+            artist,
+            album: text("album"),
+            track: text("track"),
+            release_year,
+        }
+    }
+}
+
+impl VideoInfo {
+    /// Returns true if the video comes from YouTube (or YouTube Music).
+    pub fn is_youtube(&self) -> bool {
+        self.media.is_youtube()
+    }
+
+    /// Release year: the platform's when it reports one, otherwise a year
+    /// hint from the title or description ([`crate::utils::extract_release_year`]).
+    pub fn release_year(&self) -> Option<u32> {
+        self.music.release_year.or_else(|| {
+            crate::utils::extract_release_year(&self.title, self.description.as_deref())
+        })
+    }
+
+    /// Value for the `date` tag: the release year when known, otherwise the
+    /// upload date as `YYYY-MM-DD`.
+    pub fn tag_date(&self) -> Option<String> {
+        self.release_year().map(|y| y.to_string()).or_else(|| {
+            self.upload_date
+                .as_deref()
+                .and_then(crate::utils::upload_date_to_id3_date)
+        })
+    }
 }
 
 /// Checks for required system dependencies.
@@ -155,13 +242,13 @@ pub fn extract_video_id(url: &str) -> Result<String> {
     )))
 }
 
-/// Retrieves information about a YouTube video.
+/// Retrieves information about a video.
 ///
 /// Uses `yt-dlp` to extract video metadata.
 ///
 /// # Arguments
 ///
-/// * `url` - The YouTube video URL
+/// * `url` - The video URL (YouTube or any other site yt-dlp supports)
 /// * `cookies_from_browser` - Optional browser name for cookie extraction
 ///
 /// # Returns
@@ -193,8 +280,37 @@ pub fn get_video_info(url: &str, cookies_from_browser: Option<&str>) -> Result<V
         return Err(YtcsError::DownloadError(full_error));
     }
 
-    let json_str = String::from_utf8_lossy(&output.stdout);
-    let data: serde_json::Value = serde_json::from_str(&json_str)?;
+    parse_video_info(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Builds a [`VideoInfo`] from yt-dlp's `--dump-json` output.
+///
+/// # Errors
+///
+/// Returns an error if the JSON is invalid, or if it lists several entries
+/// (a Bandcamp album or SoundCloud set prints one JSON object per track)
+pub fn parse_video_info(json_str: &str) -> Result<VideoInfo> {
+    let mut entries: Vec<serde_json::Value> = serde_json::Deserializer::from_str(json_str)
+        .into_iter()
+        .collect::<std::result::Result<_, _>>()?;
+    if entries.len() > 1 {
+        let name = entries[0]["playlist_title"]
+            .as_str()
+            .or_else(|| entries[0]["playlist"].as_str())
+            .map(|t| format!(" \"{}\"", t))
+            .unwrap_or_default();
+        return Err(YtcsError::DownloadError(format!(
+            "This URL{} lists {} tracks (an album or set), not a single video; process it \
+             with --as-album, or mode=album in a batch list",
+            name,
+            entries.len()
+        )));
+    }
+    let Some(data) = entries.pop() else {
+        return Err(YtcsError::DownloadError(
+            "yt-dlp printed no video information".to_string(),
+        ));
+    };
 
     let title = data["title"]
         .as_str()
@@ -203,7 +319,10 @@ pub fn get_video_info(url: &str, cookies_from_browser: Option<&str>) -> Result<V
 
     let duration = data["duration"].as_f64().unwrap_or(0.0);
 
-    let video_id = data["id"].as_str().unwrap_or("").to_string();
+    let media = MediaId::from_ytdlp_json(&data).unwrap_or_else(|| MediaId {
+        extractor: "generic".to_string(),
+        id: String::new(),
+    });
 
     let description = data["description"]
         .as_str()
@@ -232,9 +351,21 @@ pub fn get_video_info(url: &str, cookies_from_browser: Option<&str>) -> Result<V
         .filter(|s| !s.is_empty())
         .map(str::to_string);
 
+    let uploader = ["channel", "uploader"]
+        .iter()
+        .find_map(|key| data[*key].as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+
+    let music = MusicMetadata::from_ytdlp_json(&data);
+
     let chapters = if let Some(chapters_array) = data["chapters"].as_array() {
         if !chapters_array.is_empty() {
-            parse_chapters_from_json(&json_str).unwrap_or_else(|_| Vec::new())
+            parse_chapters_from_json(json_str).unwrap_or_else(|e| {
+                log::warn!("Ignoring the video's chapters: {}", e);
+                Vec::new()
+            })
         } else {
             Vec::new()
         }
@@ -246,12 +377,14 @@ pub fn get_video_info(url: &str, cookies_from_browser: Option<&str>) -> Result<V
         title,
         duration,
         chapters,
-        video_id,
+        media,
         description,
         upload_date,
         genre,
         webpage_url,
         thumbnail,
+        uploader,
+        music,
     })
 }
 
@@ -382,13 +515,9 @@ fn try_fetch_thumbnail_body(agent: &ureq::Agent, url: &str) -> FetchOutcome {
     }
 }
 
+/// Thumbnail URLs to try, best first: yt-dlp's `thumbnail` field, then (for
+/// YouTube only) the `i.ytimg.com` / `img.youtube.com` CDN sizes.
 fn thumbnail_candidate_urls(info: &VideoInfo, page_url: &str) -> Result<Vec<String>> {
-    let video_id = if !info.video_id.is_empty() {
-        info.video_id.clone()
-    } else {
-        extract_video_id(page_url)?
-    };
-
     let mut urls = Vec::new();
     let mut seen = std::collections::HashSet::<String>::new();
     let mut push_unique = |u: String| {
@@ -404,6 +533,14 @@ fn thumbnail_candidate_urls(info: &VideoInfo, page_url: &str) -> Result<Vec<Stri
         }
     }
 
+    if !info.is_youtube() {
+        return Ok(urls);
+    }
+    let video_id = if !info.media.id.is_empty() {
+        info.media.id.clone()
+    } else {
+        extract_video_id(page_url)?
+    };
     for base in ["https://i.ytimg.com/vi", "https://img.youtube.com/vi"] {
         for name in ["maxresdefault", "hqdefault", "mqdefault"] {
             push_unique(format!("{}/{}/{}.jpg", base, video_id, name));
@@ -550,12 +687,17 @@ pub fn download_thumbnail(url: &str, output_dir: &Path) -> Result<PathBuf> {
         title: String::new(),
         duration: 0.0,
         chapters: Vec::new(),
-        video_id,
+        media: MediaId {
+            extractor: "youtube".to_string(),
+            id: video_id,
+        },
         description: None,
         upload_date: None,
         genre: None,
         webpage_url: None,
         thumbnail: None,
+        uploader: None,
+        music: MusicMetadata::default(),
     };
     download_thumbnail_from_info(&info, url, output_dir, None)
 }
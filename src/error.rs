@@ -96,8 +96,8 @@ pub enum YtcsError {
     #[error("Chapter parsing error: {0}")]
     ChapterError(String),
 
-    /// Invalid or malformed YouTube URL.
-    #[error("Invalid YouTube URL: {0}")]
+    /// Invalid or malformed URL.
+    #[error("Invalid URL: {0}")]
     InvalidUrl(String),
 
     /// Missing required system tools (`yt-dlp`, `ffmpeg`).
@@ -128,6 +128,12 @@ pub enum YtcsError {
     #[error("Input required: {0}")]
     InputRequired(String),
 
+    /// A multi-video batch finished, but some entries failed. Each failure has
+    /// already been reported in the batch summary; exits with code 3 when at
+    /// least one entry succeeded (partial success), 1 otherwise.
+    #[error("{failed} of {total} videos failed")]
+    BatchIncomplete { failed: usize, total: usize },
+
     /// Installation error for dependencies.
     #[error("Installation error: {0}")]
     InstallError(String),
@@ -137,6 +143,18 @@ pub enum YtcsError {
     Other(String),
 }
 
+impl YtcsError {
+    /// Process exit code for this error: 2 when input was required under
+    /// `--non-interactive`, 3 for a partially successful batch, 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            YtcsError::InputRequired(_) => 2,
+            YtcsError::BatchIncomplete { failed, total } if failed < total => 3,
+            _ => 1,
+        }
+    }
+}
+
 /// Type alias for `Result<T, YtcsError>`.
 ///
 /// Simplifies function signatures by using our custom error type.
@@ -58,25 +58,43 @@
 //!
 //! - [`error`] - Custom error handling
 //! - [`chapters`] - Chapter structures and parsing
+//! - [`chapter_filter`] - Leaving chapters out by title (drop or merge)
+//! - [`chapter_review`] - Editing the chapter plan before splitting
+//! - [`chapter_validation`] - Sanity checks on a chapter list
+//! - [`comment_tracklist`] - Chapter timestamps from the comments of a video
 //! - [`downloader`] - Video downloading and metadata
+//! - [`media_url`] - Site-agnostic URL identity (YouTube and other yt-dlp sites)
 //! - [`audio`] - Audio processing and splitting
+//! - [`batch`] - Worker pool and progress display for playlist batches
 //! - [`utils`] - Utility functions (formatting, cleaning)
+//! - [`title_rules`] - User-defined title cleanup rules
 //! - [`config`] - Configuration management
 //! - [`playlist`] - Playlist detection and handling
+//! - [`playlist_selection`] - Choosing which playlist entries to process
+//! - [`sponsorblock`] - SponsorBlock segment lookup, removal and marking
 
 pub mod audio;
+pub mod batch;
+pub mod chapter_filter;
 pub mod chapter_refinement;
+pub mod chapter_review;
+pub mod chapter_validation;
 pub mod chapters;
 pub mod chapters_from_description;
+pub mod comment_tracklist;
 pub mod config;
 pub mod cookie_helper;
 pub mod dependency;
 pub mod downloader;
 pub mod error;
 pub mod error_handler;
+pub mod media_url;
 pub mod playlist;
+pub mod playlist_selection;
 pub mod progress;
+pub mod sponsorblock;
 pub mod temp_file;
+pub mod title_rules;
 pub mod ui;
 pub mod utils;
 pub mod yt_dlp_progress;
@@ -85,7 +103,10 @@ pub mod ytdlp_error_parser;
 pub mod ytdlp_helper;
 
 pub use chapters::Chapter;
-pub use config::{AudioFormat, Config};
+pub use config::{
+    AudioFormat, ChapteredFormat, Config, ExcludedChapters, OutputMode, RefineStrategy,
+    SponsorBlockMode,
+};
 pub use downloader::VideoInfo;
 pub use error::{MissingToolsError, Result, YtcsError};
 pub use ui::MetadataSource;
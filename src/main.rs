@@ -4,8 +4,11 @@ use std::io::Write;
 use std::path::PathBuf;
 use ui::MetadataSource;
 use youtube_chapter_splitter::{
-    Result, YtcsError, audio, chapter_refinement, chapters_from_description, config, downloader,
-    playlist, ui, utils, yt_dlp_progress, yt_dlp_progress::YtdlpDownloadOpts,
+    Result, YtcsError, audio, batch, chapter_filter::ChapterFilter, chapter_refinement,
+    chapter_review, chapter_review::ChapterReview, chapter_validation, chapters,
+    chapters_from_description, comment_tracklist, config, downloader, media_url, playlist,
+    playlist_selection, playlist_selection::PlaylistSelection, sponsorblock, temp_file::TempFile,
+    title_rules, ui, utils, yt_dlp_progress, yt_dlp_progress::YtdlpDownloadOpts,
 };
 
 #[derive(Parser)]
@@ -17,9 +20,15 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// YouTube video URL
+    /// Video URL (YouTube, or any site yt-dlp supports: SoundCloud, Bandcamp, Vimeo, …)
+    #[arg(conflicts_with = "batch_file")]
     url: Option<String>,
 
+    /// Read URLs from a file (`-` for stdin): one per line, `#` comments,
+    /// optional `artist=` / `album=` / `chapters=<file>` overrides per line
+    #[arg(long, value_name = "PATH")]
+    batch_file: Option<String>,
+
     /// Output directory (overrides config default_output_dir)
     #[arg(short, long)]
     output: Option<String>,
@@ -36,6 +45,79 @@ struct Cli {
     #[arg(long)]
     refine_chapters: bool,
 
+    /// Number of tracks to find when falling back to silence detection
+    /// (thresholds are searched until that many tracks are found)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    tracks: Option<u32>,
+
+    /// Noise threshold in dB for finding tracks by silence (overrides config silence_noise_db)
+    #[arg(long, value_name = "DB", allow_hyphen_values = true)]
+    silence_noise: Option<f64>,
+
+    /// Minimum silence (seconds) between tracks found by silence
+    /// (overrides config silence_min_duration)
+    #[arg(long, value_name = "SECS")]
+    min_silence: Option<f64>,
+
+    /// Tracks found by silence shorter than this join a neighbour; 0 keeps them
+    /// (overrides config min_track_duration)
+    #[arg(long, value_name = "SECS")]
+    min_track: Option<f64>,
+
+    /// Stop instead of splitting when silence detection finds more tracks
+    /// (overrides config max_tracks)
+    #[arg(long, value_name = "N")]
+    max_tracks: Option<usize>,
+
+    /// Look for a tracklist in the top comments when the description has none
+    /// (YouTube only; overrides config comment_tracklists)
+    #[arg(long)]
+    comment_tracklists: bool,
+
+    /// Where refined cuts go: the nearest silence, or the quietest point for
+    /// crossfaded mixes (overrides config refine_strategy)
+    #[arg(long, value_name = "STRATEGY", value_parser = ["silence", "energy"])]
+    refine_strategy: Option<String>,
+
+    /// Refined cuts may move at most this many seconds; further moves keep
+    /// the declared time (0 = no limit; overrides config refine_max_delta)
+    #[arg(long, value_name = "SECONDS")]
+    refine_max_delta: Option<f64>,
+
+    /// Refined cuts scored below this confidence (0-1) keep the declared
+    /// time (overrides config refine_min_confidence)
+    #[arg(long, value_name = "SCORE")]
+    refine_min_confidence: Option<f64>,
+
+    /// Show the chapter plan and edit it (rename, merge, split, delete, move
+    /// cuts) before splitting (overrides config review_chapters)
+    #[arg(long, conflicts_with = "non_interactive")]
+    review: bool,
+
+    /// Only chapters whose title matches this regex become tracks
+    /// (case-insensitive; overrides config chapter_include)
+    #[arg(long, value_name = "REGEX")]
+    include_chapters: Option<String>,
+
+    /// Chapters whose title matches this regex don't become tracks, e.g.
+    /// `^(intro|outro)` (case-insensitive; overrides config chapter_exclude)
+    #[arg(long, value_name = "REGEX")]
+    exclude_chapters: Option<String>,
+
+    /// Merge left-out chapters into the neighbouring track instead of dropping
+    /// their audio (overrides config excluded_chapters)
+    #[arg(long)]
+    merge_excluded: bool,
+
+    /// SponsorBlock segments of YouTube videos: cut them out of the audio,
+    /// make them chapters of their own, or ignore them (overrides config sponsorblock)
+    #[arg(long, value_name = "MODE", value_parser = ["remove", "mark", "off"])]
+    sponsorblock: Option<String>,
+
+    /// Open the chapter list in $EDITOR (`HH:MM:SS Title` lines) before splitting
+    #[arg(long, conflicts_with = "non_interactive")]
+    edit_chapters: bool,
+
     /// Print target folder and chapter plan without downloading or splitting
     #[arg(long)]
     dry_run: bool,
@@ -48,13 +130,61 @@ struct Cli {
     #[arg(long)]
     no_cover: bool,
 
+    /// Write one file with chapter markers instead of one file per track
+    /// (container from config `single_file_format`, default m4b)
+    #[arg(long)]
+    single_file: bool,
+
+    /// Treat a playlist URL as one album: each video becomes a numbered track
+    #[arg(long)]
+    as_album: bool,
+
     /// Use existing `temp_audio.*` in the album folder if present instead of downloading
     #[arg(long)]
     skip_download: bool,
 
+    /// Only process these playlist entries, e.g. `1-5,8,10-`
+    #[arg(long, value_name = "SPEC")]
+    items: Option<String>,
+
+    /// Only process playlist entries whose title matches this regex (case-insensitive)
+    #[arg(long, value_name = "REGEX")]
+    match_title: Option<String>,
+
+    /// Skip playlist entries whose title matches this regex (case-insensitive)
+    #[arg(long, value_name = "REGEX")]
+    reject_title: Option<String>,
+
+    /// Skip playlist entries shorter than this (seconds, m:ss or h:mm:ss)
+    #[arg(long, value_name = "TIME")]
+    min_duration: Option<String>,
+
+    /// Skip playlist entries longer than this (seconds, m:ss or h:mm:ss)
+    #[arg(long, value_name = "TIME")]
+    max_duration: Option<String>,
+
+    /// Process the selected playlist entries last to first
+    #[arg(long)]
+    reverse: bool,
+
+    /// Process the selected playlist entries without showing the checklist
+    #[arg(long)]
+    no_pick: bool,
+
+    /// Number of playlist entries to process at the same time
+    /// (overrides config parallel_downloads)
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Where to write the URLs that failed in a multi-video batch
+    /// (default: ytcs-failed.txt in the output directory)
+    #[arg(long, value_name = "PATH")]
+    failures_file: Option<PathBuf>,
+
     /// Never read from stdin; fail instead of prompting (playlist choice, missing
     /// artist/album, dependency install, yt-dlp update). Exit code 2 means a
-    /// prompt was needed; exit code 1 is any other error.
+    /// prompt was needed; exit code 3 means a multi-video batch finished with
+    /// some entries failed; exit code 1 is any other error.
     #[arg(long)]
     non_interactive: bool,
 }
@@ -67,11 +197,12 @@ enum Commands {
         #[arg(long, short = 's')]
         show: bool,
     },
-}
-
-fn canonical_video_url(url: &str) -> Result<String> {
-    let id = downloader::extract_video_id(url)?;
-    Ok(format!("https://www.youtube.com/watch?v={}", id))
+    /// Show how a title is cleaned into artist, album, folder and track names
+    /// with the current config (including `[title_rules]`)
+    TestTitle {
+        /// The video or chapter title to clean
+        title: String,
+    },
 }
 
 fn is_playlist_only_page(url: &str) -> bool {
@@ -83,14 +214,16 @@ fn is_playlist_only_page(url: &str) -> bool {
 fn resolve_video_urls(
     raw: &str,
     cfg: &config::Config,
-    non_interactive: bool,
+    cli: &Cli,
+    selection: &PlaylistSelection,
 ) -> Result<Vec<String>> {
+    let non_interactive = cli.non_interactive;
     use config::PlaylistBehavior;
     let cookies = cfg.cookies_from_browser.as_deref();
 
-    if playlist::is_playlist_url(raw).is_none() {
-        log::info!("Single video URL (no playlist parameter)");
-        return Ok(vec![canonical_video_url(raw)?]);
+    if !media_url::is_youtube_url(raw) || playlist::is_playlist_url(raw).is_none() {
+        log::info!("Single video URL (no YouTube playlist parameter)");
+        return Ok(vec![media_url::canonical_media_url(raw)?]);
     }
 
     log::info!(
@@ -106,14 +239,15 @@ fn resolve_video_urls(
                         .to_string(),
                 ));
             }
-            Ok(vec![canonical_video_url(
+            Ok(vec![media_url::canonical_media_url(
                 &playlist::remove_playlist_param(raw),
             )?])
         }
         PlaylistBehavior::PlaylistOnly => {
             let info = playlist::get_playlist_info(raw, cookies)?;
             log::info!("Playlist expanded to {} videos", info.videos.len());
-            Ok(info.videos.iter().map(|v| v.url.clone()).collect())
+            let videos = select_playlist_entries(&info, cli, selection, None)?;
+            Ok(videos.iter().map(|v| v.url.clone()).collect())
         }
         PlaylistBehavior::Ask => {
             if non_interactive {
@@ -135,14 +269,15 @@ fn resolve_video_urls(
             if input.trim().eq_ignore_ascii_case("y") {
                 let info = playlist::get_playlist_info(raw, cookies)?;
                 log::info!("User chose full playlist ({} videos)", info.videos.len());
-                Ok(info.videos.iter().map(|v| v.url.clone()).collect())
+                let videos = select_playlist_entries(&info, cli, selection, None)?;
+                Ok(videos.iter().map(|v| v.url.clone()).collect())
             } else if is_playlist_only_page(raw) {
                 Err(YtcsError::InvalidUrl(
                     "Playlist-only URLs cannot be reduced to one video; answer y to download the playlist."
                         .to_string(),
                 ))
             } else {
-                Ok(vec![canonical_video_url(
+                Ok(vec![media_url::canonical_media_url(
                     &playlist::remove_playlist_param(raw),
                 )?])
             }
@@ -150,6 +285,119 @@ fn resolve_video_urls(
     }
 }
 
+/// Builds the playlist entry selection from `--items`, `--match-title`,
+/// `--reject-title`, `--min-duration`, `--max-duration` and `--reverse`.
+fn playlist_selection(cli: &Cli) -> Result<PlaylistSelection> {
+    Ok(PlaylistSelection {
+        items: cli
+            .items
+            .as_deref()
+            .map(playlist_selection::parse_item_ranges)
+            .transpose()?,
+        match_title: cli
+            .match_title
+            .as_deref()
+            .map(playlist_selection::title_pattern)
+            .transpose()?,
+        reject_title: cli
+            .reject_title
+            .as_deref()
+            .map(playlist_selection::title_pattern)
+            .transpose()?,
+        min_duration: cli
+            .min_duration
+            .as_deref()
+            .map(playlist_selection::parse_duration_limit)
+            .transpose()?,
+        max_duration: cli
+            .max_duration
+            .as_deref()
+            .map(playlist_selection::parse_duration_limit)
+            .transpose()?,
+        reverse: cli.reverse,
+    })
+}
+
+/// Checks the silence-detection and refinement options with the rules of
+/// their config keys.
+fn check_cli_overrides(cli: &Cli, cfg: &config::Config) -> Result<()> {
+    config::Config {
+        silence_noise_db: cli.silence_noise.unwrap_or(cfg.silence_noise_db),
+        silence_min_duration: cli.min_silence.unwrap_or(cfg.silence_min_duration),
+        min_track_duration: cli.min_track.unwrap_or(cfg.min_track_duration),
+        max_tracks: cli.max_tracks.unwrap_or(cfg.max_tracks),
+        refine_max_delta: cli.refine_max_delta.unwrap_or(cfg.refine_max_delta),
+        refine_min_confidence: cli
+            .refine_min_confidence
+            .unwrap_or(cfg.refine_min_confidence),
+        ..cfg.clone()
+    }
+    .validate()
+}
+
+/// Builds the chapter include/exclude filter from the CLI flags and config.
+fn chapter_filter(cli: &Cli, cfg: &config::Config) -> Result<ChapterFilter> {
+    ChapterFilter::new(
+        cli.include_chapters
+            .as_deref()
+            .or(cfg.chapter_include.as_deref()),
+        cli.exclude_chapters
+            .as_deref()
+            .or(cfg.chapter_exclude.as_deref()),
+        if cli.merge_excluded {
+            config::ExcludedChapters::Merge
+        } else {
+            cfg.excluded_chapters
+        },
+    )
+}
+
+/// Applies the selection to a fetched playlist, then, in an interactive run on
+/// a terminal, lets the user untick entries from a checklist (`--no-pick` to skip).
+fn select_playlist_entries(
+    info: &playlist::PlaylistInfo,
+    cli: &Cli,
+    selection: &PlaylistSelection,
+    parallel: Option<&ParallelCtx>,
+) -> Result<Vec<playlist::PlaylistVideo>> {
+    let mut videos = selection.apply(&info.videos);
+    if !selection.is_all() {
+        log::info!(
+            "Playlist selection kept {} of {} videos",
+            videos.len(),
+            info.videos.len()
+        );
+    }
+    if videos.len() > 1
+        && !cli.no_pick
+        && !cli.non_interactive
+        && std::io::IsTerminal::is_terminal(&std::io::stdin())
+    {
+        videos = match parallel {
+            Some(p) => p.progress.suspend(|| ui::prompt_playlist_pick(&videos)),
+            None => ui::prompt_playlist_pick(&videos),
+        };
+    }
+    if videos.is_empty() {
+        return Err(YtcsError::Other(format!(
+            "No entries of playlist \"{}\" left after selection",
+            info.title
+        )));
+    }
+    Ok(videos)
+}
+
+/// Artist guessed from the uploader's channel, when `channel_as_artist` is on.
+fn channel_artist(info: &downloader::VideoInfo, cfg: &config::Config) -> Option<String> {
+    if !cfg.channel_as_artist {
+        return None;
+    }
+    info.uploader
+        .as_deref()
+        .and_then(utils::artist_from_channel)
+        .map(|a| utils::clean_folder_name(&a))
+}
+
 /// Progress callback for track splitting
 fn track_progress_callback(track_number: usize, total_tracks: usize, title: &str, duration: &str) {
     ui::print_track_progress(track_number, total_tracks, title, duration);
@@ -206,17 +454,58 @@ fn handle_missing_dependencies(
 }
 
 /// When `total > 1`, used with `playlist_prefix_index` to disambiguate output folders.
-struct BatchCtx {
+struct BatchCtx<'a> {
     index: usize,
     total: usize,
+    /// Set when entries run concurrently (see `run_parallel_batch`)
+    parallel: Option<ParallelCtx<'a>>,
+}
+
+/// Shared state for one worker of a parallel batch.
+struct ParallelCtx<'a> {
+    progress: &'a batch::BatchProgress,
+    line: indicatif::ProgressBar,
+    split_slots: &'a batch::Semaphore,
+    /// Output folders in use; see [`batch::DirLocks`]
+    dir_locks: &'a batch::DirLocks,
 }
 
-fn run_dry_run(urls: &[String], cli: &Cli, cfg: &config::Config) -> Result<()> {
-    for (i, url) in urls.iter().enumerate() {
+fn run_dry_run(jobs: &[batch::VideoJob], cli: &Cli, cfg: &config::Config) -> Result<()> {
+    for (i, job) in jobs.iter().enumerate() {
+        let url = &job.url;
+        if job.as_album {
+            let info = playlist::get_playlist_info(url, cfg.cookies_from_browser.as_deref())?;
+            let videos = playlist_selection(cli)?.apply(&info.videos);
+            println!("URL         {}", url);
+            println!("  album     {}", info.album_title());
+            println!("  tracks    {} (one per video)", videos.len());
+            println!("  format    {:?}", cfg.audio_format);
+            if i + 1 < jobs.len() {
+                println!();
+            }
+            continue;
+        }
         let vi = downloader::get_video_info(url, cfg.cookies_from_browser.as_deref())?;
-        let ((artist, album), _, _) = utils::parse_artist_album_with_source(&vi.title);
-        let mut folder_name = cfg.format_directory(&artist, &album);
-        if urls.len() > 1 && cfg.playlist_prefix_index {
+        let ((mut parsed_artist, parsed_album), artist_src, _) = utils::artist_album_from_info(&vi);
+        if artist_src == MetadataSource::Default
+            && let Some(a) = channel_artist(&vi, cfg)
+        {
+            parsed_artist = a;
+        }
+        let artist = job
+            .artist
+            .as_ref()
+            .or(cli.artist.as_ref())
+            .map(|a| utils::clean_folder_name(a))
+            .unwrap_or(parsed_artist);
+        let album = job
+            .album
+            .as_ref()
+            .or(cli.album.as_ref())
+            .map(|a| utils::clean_folder_name(a))
+            .unwrap_or(parsed_album);
+        let mut folder_name = cfg.format_directory_with_year(&artist, &album, vi.release_year());
+        if jobs.len() > 1 && cfg.playlist_prefix_index {
             folder_name = format!("{:02} - {}", i + 1, folder_name);
         }
         let base = cli
@@ -225,8 +514,18 @@ fn run_dry_run(urls: &[String], cli: &Cli, cfg: &config::Config) -> Result<()> {
             .map(PathBuf::from)
             .unwrap_or_else(|| cfg.get_output_dir());
         let out_dir = base.join(&folder_name);
-        let chapter_note = if !vi.chapters.is_empty() {
-            format!("{} (YouTube chapters)", vi.chapters.len())
+        let filter = chapter_filter(cli, cfg)?;
+        let kept = |chapters: &[chapters::Chapter], source: &str| match filter.apply(chapters) {
+            Ok(k) if k.len() != chapters.len() => {
+                format!("{} ({}), {} after filters", chapters.len(), source, k.len())
+            }
+            Ok(_) => format!("{} ({})", chapters.len(), source),
+            Err(e) => format!("{} ({}), {}", chapters.len(), source, e),
+        };
+        let chapter_note = if let Some(path) = &job.chapters {
+            format!("from {}", path.display())
+        } else if !vi.chapters.is_empty() {
+            kept(&vi.chapters, "YouTube chapters")
         } else if let Some(desc) = vi
             .description
             .as_deref()
@@ -234,37 +533,190 @@ fn run_dry_run(urls: &[String], cli: &Cli, cfg: &config::Config) -> Result<()> {
             .filter(|d| !d.is_empty())
         {
             match chapters_from_description::parse_chapters_from_description(desc, vi.duration) {
-                Ok(c) if c.len() >= 2 => format!("{} (from description)", c.len()),
-                _ => "silence detection after download".to_string(),
+                Ok(c) if c.len() >= 2 => kept(&c, "from description"),
+                _ => match find_comment_tracklist(url, &vi, cli, cfg) {
+                    Some(t) => kept(&t.chapters, "from a comment"),
+                    None => silence_note(cli, &vi),
+                },
             }
+        } else if let Some(t) = find_comment_tracklist(url, &vi, cli, cfg) {
+            kept(&t.chapters, "from a comment")
         } else {
-            "silence detection after download".to_string()
+            silence_note(cli, &vi)
         };
         println!("URL         {}", url);
         println!("  output    {}", out_dir.display());
         println!("  chapters  {}", chapter_note);
-        println!("  format    {:?}", cfg.audio_format);
-        if i + 1 < urls.len() {
+        if cli.single_file || cfg.output_mode == config::OutputMode::SingleFile {
+            println!("  format    {:?} (single file)", cfg.single_file_format);
+        } else {
+            println!("  format    {:?}", cfg.audio_format);
+        }
+        if i + 1 < jobs.len() {
             println!();
         }
     }
     Ok(())
 }
 
+/// Titles listed without times in the description, if any.
+fn untimed_titles(video_info: &downloader::VideoInfo) -> Option<Vec<String>> {
+    video_info
+        .description
+        .as_deref()
+        .and_then(chapters_from_description::parse_untimed_tracklist)
+}
+
+/// Dry-run description of the silence-detection fallback.
+fn silence_note(cli: &Cli, video_info: &downloader::VideoInfo) -> String {
+    match (cli.tracks, untimed_titles(video_info)) {
+        (Some(n), _) => format!("silence detection after download ({} tracks)", n),
+        (None, Some(titles)) => format!(
+            "silence detection after download ({} titles from description)",
+            titles.len()
+        ),
+        (None, None) => "silence detection after download".to_string(),
+    }
+}
+
+/// Chapters from silence detection, for exactly `--tracks` tracks or as many
+/// as the description lists titles, which then name the tracks. Otherwise
+/// tracks shorter than `min_track_duration` are merged away. More than
+/// `max_tracks` tracks is an error.
+fn detect_silence_tracks(
+    audio_file: &std::path::Path,
+    cli: &Cli,
+    cfg: &config::Config,
+    titles: Option<Vec<String>>,
+) -> Result<Vec<chapters::Chapter>> {
+    let min_track = cli.min_track.unwrap_or(cfg.min_track_duration);
+    let max_tracks = cli.max_tracks.unwrap_or(cfg.max_tracks);
+    let chapters = find_silence_tracks(audio_file, cli, cfg, titles, min_track)?;
+    if chapters.len() > max_tracks {
+        return Err(YtcsError::ChapterError(format!(
+            "Silence detection found {} tracks, more than max_tracks ({}). \
+             Try a stricter --silence-noise, a longer --min-silence or --tracks N",
+            chapters.len(),
+            max_tracks
+        )));
+    }
+    Ok(chapters)
+}
+
+fn find_silence_tracks(
+    audio_file: &std::path::Path,
+    cli: &Cli,
+    cfg: &config::Config,
+    titles: Option<Vec<String>>,
+    min_track: f64,
+) -> Result<Vec<chapters::Chapter>> {
+    let name = |chapters: &mut Vec<chapters::Chapter>, titles: &[String]| {
+        for (chapter, title) in chapters.iter_mut().zip(titles) {
+            chapter.title = title.clone();
+        }
+    };
+    if let Some(n) = cli.tracks {
+        let mut chapters =
+            audio::detect_silence_chapters_for_count(audio_file, n as usize, min_track)?;
+        match titles {
+            Some(titles) if titles.len() == chapters.len() => name(&mut chapters, &titles),
+            Some(titles) => log::warn!(
+                "--tracks {} differs from the {} titles in the description; tracks stay unnamed",
+                n,
+                titles.len()
+            ),
+            None => {}
+        }
+        return Ok(chapters);
+    }
+    if let Some(titles) = titles {
+        match audio::detect_silence_chapters_for_count(audio_file, titles.len(), min_track) {
+            Ok(mut chapters) => {
+                name(&mut chapters, &titles);
+                return Ok(chapters);
+            }
+            Err(e) => log::warn!("{}; the description's titles are not used", e),
+        }
+    }
+    let chapters = audio::detect_silence_chapters(
+        audio_file,
+        cli.silence_noise.unwrap_or(cfg.silence_noise_db),
+        cli.min_silence.unwrap_or(cfg.silence_min_duration),
+    )?;
+    Ok(audio::merge_short_tracks(&chapters, min_track))
+}
+
+/// The best tracklist found in the top comments, when comment tracklists are
+/// enabled (YouTube only). A failed lookup is only logged.
+fn find_comment_tracklist(
+    url: &str,
+    video_info: &downloader::VideoInfo,
+    cli: &Cli,
+    cfg: &config::Config,
+) -> Option<comment_tracklist::CommentTracklist> {
+    if !(cli.comment_tracklists || cfg.comment_tracklists) || !video_info.is_youtube() {
+        return None;
+    }
+    let comments = match comment_tracklist::fetch_comments(
+        url,
+        cfg.cookies_from_browser.as_deref(),
+        cfg.max_comments,
+    ) {
+        Ok(comments) => comments,
+        Err(e) => {
+            log::warn!("Could not fetch comments: {}", e);
+            return None;
+        }
+    };
+    let best = comment_tracklist::best_tracklist(&comments, video_info.duration);
+    if best.is_none() {
+        log::info!("No tracklist in {} comments", comments.len());
+    }
+    best
+}
+
 fn process_single_video(
-    video_url: &str,
+    job: &batch::VideoJob,
     cli: &Cli,
     app_config: &config::Config,
     batch: Option<BatchCtx>,
-) -> Result<()> {
-    let clean_url = video_url.to_string();
+) -> Result<batch::VideoOutcome> {
+    if job.as_album {
+        return process_playlist_album(job, cli, app_config, batch);
+    }
+    let clean_url = job.url.clone();
+    // A batch-list line's overrides win over the command-line ones.
+    let forced_artist = job.artist.as_ref().or(cli.artist.as_ref());
+    let forced_album = job.album.as_ref().or(cli.album.as_ref());
+    let parallel = batch.as_ref().and_then(|b| b.parallel.as_ref());
+    let set_status = |msg: &str| {
+        if let Some(p) = parallel {
+            p.line.set_message(msg.to_string());
+        }
+    };
+    let prompt_metadata = |title: &str, artist: &str, album: &str| match parallel {
+        Some(p) => p
+            .progress
+            .suspend(|| ui::prompt_metadata(title, artist, album)),
+        None => ui::prompt_metadata(title, artist, album),
+    };
+
+    set_status("fetching video information");
 
     ui::print_section_header("Fetching video information");
-    let video_info =
+    let mut video_info =
         downloader::get_video_info(&clean_url, app_config.cookies_from_browser.as_deref())?;
+    if let Some(path) = &job.chapters {
+        // A tracklist given for this URL takes the place of YouTube's chapters.
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            YtcsError::ChapterError(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        video_info.chapters =
+            chapters_from_description::parse_chapters_from_description(&text, video_info.duration)?;
+    }
 
     let (mut artist, mut album, mut artist_source, mut album_source) = if let (Some(a), Some(al)) =
-        (&cli.artist, &cli.album)
+        (forced_artist, forced_album)
     {
         (
             utils::clean_folder_name(a),
@@ -272,29 +724,34 @@ fn process_single_video(
             MetadataSource::Forced,
             MetadataSource::Forced,
         )
-    } else if let Some(a) = &cli.artist {
-        let ((_, parsed_album), _, _) = utils::parse_artist_album_with_source(&video_info.title);
+    } else if let Some(a) = forced_artist {
+        let ((_, parsed_album), _, album_src) = utils::artist_album_from_info(&video_info);
         (
             utils::clean_folder_name(a),
             parsed_album,
             MetadataSource::Forced,
-            MetadataSource::Detected,
+            album_src,
         )
-    } else if let Some(al) = &cli.album {
-        let ((parsed_artist, _), _, _) = utils::parse_artist_album_with_source(&video_info.title);
+    } else if let Some(al) = forced_album {
+        let ((parsed_artist, _), artist_src, _) = utils::artist_album_from_info(&video_info);
         (
             parsed_artist,
             utils::clean_folder_name(al),
-            MetadataSource::Detected,
+            artist_src,
             MetadataSource::Forced,
         )
     } else {
-        let ((artist, album), artist_src, album_src) =
-            utils::parse_artist_album_with_source(&video_info.title);
+        let ((artist, album), artist_src, album_src) = utils::artist_album_from_info(&video_info);
         (artist, album, artist_src, album_src)
     };
+    if artist_source == MetadataSource::Default
+        && let Some(channel_artist) = channel_artist(&video_info, app_config)
+    {
+        artist = channel_artist;
+        artist_source = MetadataSource::Channel;
+    }
 
-    if artist == "Unknown Artist" && cli.artist.is_none() {
+    if artist == "Unknown Artist" && forced_artist.is_none() {
         if cli.non_interactive {
             return Err(YtcsError::InputRequired(format!(
                 "Could not determine the artist for \"{}\"; pass --artist (and --album if \
@@ -302,7 +759,7 @@ fn process_single_video(
                 video_info.title
             )));
         }
-        let (input_artist, input_album) = ui::prompt_metadata(
+        let (input_artist, input_album) = prompt_metadata(
             &video_info.title,
             &artist,
             &utils::clean_folder_name(&video_info.title),
@@ -312,8 +769,12 @@ fn process_single_video(
         artist_source = MetadataSource::Forced;
         album_source = MetadataSource::Forced;
     } else if album == utils::clean_folder_name(&video_info.title)
-        && cli.album.is_none()
+        && forced_album.is_none()
+        && album_source != MetadataSource::Platform
         && !utils::clean_folder_name(&video_info.title).contains(" - ")
+        // With the channel standing in for the artist, an unattended run
+        // keeps the title as the album rather than stopping.
+        && !(cli.non_interactive && artist_source == MetadataSource::Channel)
     {
         if cli.non_interactive {
             return Err(YtcsError::InputRequired(format!(
@@ -322,7 +783,7 @@ fn process_single_video(
                 video_info.title
             )));
         }
-        let (input_artist, input_album) = ui::prompt_metadata(&video_info.title, &artist, &album);
+        let (input_artist, input_album) = prompt_metadata(&video_info.title, &artist, &album);
         artist = input_artist;
         album = input_album;
         artist_source = MetadataSource::Forced;
@@ -339,7 +800,8 @@ fn process_single_video(
         album_source,
     );
 
-    let mut folder_name = app_config.format_directory(&artist, &album);
+    let mut folder_name =
+        app_config.format_directory_with_year(&artist, &album, video_info.release_year());
     if let Some(b) = &batch
         && b.total > 1
         && app_config.playlist_prefix_index
@@ -352,6 +814,22 @@ fn process_single_video(
         .map(std::path::PathBuf::from)
         .unwrap_or_else(|| app_config.get_output_dir());
     let output_dir = base_output.join(&folder_name);
+    // Entries landing in the same folder share its scratch files.
+    let _dir_lock = parallel.map(|p| {
+        p.line.set_message("waiting for the output folder");
+        p.dir_locks.lock(&output_dir)
+    });
+
+    if !app_config.overwrite_existing
+        && let Some(reason) =
+            existing_output(cli, app_config, &video_info, &output_dir, &artist, &album)
+    {
+        match parallel {
+            Some(p) => p.line.finish_with_message(format!("- skipped: {}", reason)),
+            None => ui::print_skipped(&reason),
+        }
+        return Ok(batch::VideoOutcome::Skipped(reason));
+    }
     std::fs::create_dir_all(&output_dir)?;
 
     let want_cover = app_config.download_cover && !cli.no_cover;
@@ -381,6 +859,8 @@ fn process_single_video(
     let temp_audio = output_dir.join(format!("temp_audio.{ext}"));
     let download_opts = YtdlpDownloadOpts {
         non_interactive: cli.non_interactive,
+        // The update prompt can't share the terminal with other workers' progress.
+        ytdlp_auto_update_on_failure: app_config.ytdlp_auto_update && parallel.is_none(),
         ..YtdlpDownloadOpts::from(app_config)
     };
 
@@ -396,71 +876,262 @@ fn process_single_video(
             )));
         }
     } else {
+        set_status(&format!("downloading \"{}\"", album));
         yt_dlp_progress::download_audio_with_progress(
             &clean_url,
             &temp_audio,
             app_config.cookies_from_browser.as_deref(),
             download_opts,
-            None,
+            parallel.map(|p| p.progress.download_bar(&p.line)),
             None,
         )?
     };
     ui::print_audio_complete(audio_file.to_str().unwrap_or("audio"));
 
-    let (mut chapters_to_use, used_silence_only) = if !video_info.chapters.is_empty() {
-        (video_info.chapters.clone(), false)
-    } else if let Some(desc) = video_info
+    // Everything from here on is ffmpeg work; cap how many videos do it at once.
+    let _split_permit = parallel.map(|p| {
+        p.line
+            .set_message(format!("\"{}\" waiting to split", album));
+        p.split_slots.acquire()
+    });
+    set_status(&format!("analyzing \"{}\"", album));
+
+    let from_description = video_info
         .description
         .as_deref()
         .map(str::trim)
         .filter(|d| !d.is_empty())
-    {
-        match chapters_from_description::parse_chapters_from_description(desc, video_info.duration)
-        {
-            Ok(c) if c.len() >= 2 => (c, false),
-            _ => (
-                audio::detect_silence_chapters(&audio_file, -30.0, 2.0)?,
-                true,
-            ),
-        }
+        .and_then(|desc| {
+            chapters_from_description::parse_chapters_from_description(desc, video_info.duration)
+                .ok()
+        })
+        .filter(|c| c.len() >= 2);
+    let (mut chapters_to_use, used_silence_only) = if !video_info.chapters.is_empty() {
+        (video_info.chapters.clone(), false)
+    } else if let Some(c) = from_description {
+        (c, false)
+    } else if let Some(t) = find_comment_tracklist(&clean_url, &video_info, cli, app_config) {
+        ui::print_comment_tracklist(&t);
+        (t.chapters, false)
     } else {
         (
-            audio::detect_silence_chapters(&audio_file, -30.0, 2.0)?,
+            detect_silence_tracks(&audio_file, cli, app_config, untimed_titles(&video_info))?,
             true,
         )
     };
 
+    let audio_duration = audio::get_audio_duration(&audio_file)
+        .inspect_err(|e| log::warn!("Cannot check the chapters against the audio: {}", e))
+        .ok();
+    let warnings = chapter_validation::validate_chapters(&chapters_to_use, audio_duration);
+    for warning in &warnings {
+        log::warn!("Chapter check: {}", warning);
+    }
+    ui::print_chapter_warnings(&warnings);
+    // Out-of-order chapters stop the run, unless `--edit-chapters` lets the
+    // user fix the order right away; the editor is not opened again below.
+    let edit_chapters = cli.edit_chapters && !cli.non_interactive;
+    let mut reordered = false;
+    chapters_to_use = chapter_validation::sanitize_chapters_or_reorder(
+        &chapters_to_use,
+        audio_duration,
+        |unsorted| {
+            if !edit_chapters {
+                return Ok(None);
+            }
+            set_status(&format!("fixing the chapter order of \"{}\"", album));
+            let edit = || {
+                chapter_review::edit_chapters_in_editor(
+                    unsorted,
+                    &video_info.title,
+                    audio_duration.unwrap_or(video_info.duration),
+                )
+            };
+            reordered = true;
+            match parallel {
+                Some(p) => p.progress.suspend(edit).map(Some),
+                None => edit().map(Some),
+            }
+        },
+    )?;
+    // Chapter markers of the kept download (`keep_source_audio`): every
+    // chapter, on the timeline of the download itself.
+    let mut full_chapters = chapters_to_use.clone();
+
+    // With `sponsorblock = "remove"` the tracks are cut from a copy without the
+    // segments; the download itself stays as is for `--skip-download`.
+    let mut split_source = audio_file.clone();
+    let mut _sponsor_free_audio = None;
+    let sponsorblock_mode = match cli.sponsorblock.as_deref() {
+        Some("remove") => config::SponsorBlockMode::Remove,
+        Some("mark") => config::SponsorBlockMode::Mark,
+        Some(_) => config::SponsorBlockMode::Off,
+        None => app_config.sponsorblock,
+    };
+    if sponsorblock_mode != config::SponsorBlockMode::Off && video_info.is_youtube() {
+        set_status(&format!(
+            "looking up SponsorBlock segments of \"{}\"",
+            album
+        ));
+        match sponsorblock::fetch_segments(
+            &app_config.sponsorblock_api,
+            &video_info.media.id,
+            &app_config.sponsorblock_categories,
+        ) {
+            Ok(segments) if segments.is_empty() => log::info!("No SponsorBlock segments"),
+            Ok(segments) => {
+                let total: f64 = segments.iter().map(|s| s.duration()).sum();
+                if sponsorblock_mode == config::SponsorBlockMode::Remove {
+                    let cut = TempFile::new(&output_dir.join("temp_audio_sponsorblock.flac"));
+                    let ranges: Vec<(f64, f64)> =
+                        segments.iter().map(|s| (s.start, s.end)).collect();
+                    audio::remove_time_ranges(&audio_file, &ranges, cut.path())?;
+                    split_source = cut.path().to_path_buf();
+                    _sponsor_free_audio = Some(cut);
+                    chapters_to_use = sponsorblock::remove_segments(&chapters_to_use, &segments);
+                    if chapters_to_use.is_empty() {
+                        return Err(YtcsError::ChapterError(
+                            "No chapters left after removing SponsorBlock segments".to_string(),
+                        ));
+                    }
+                    ui::print_sponsorblock_segments("Removed", segments.len(), total);
+                } else {
+                    chapters_to_use = sponsorblock::mark_segments(&chapters_to_use, &segments);
+                    ui::print_sponsorblock_segments("Marked", segments.len(), total);
+                }
+            }
+            Err(e) => log::warn!("SponsorBlock lookup failed, keeping all audio: {}", e),
+        }
+    }
+
+    let mut declared_chapters = chapters_to_use.clone();
     if !used_silence_only && (cli.refine_chapters || app_config.refine_chapters) {
+        let strategy = match cli.refine_strategy.as_deref() {
+            Some("energy") => config::RefineStrategy::Energy,
+            Some(_) => config::RefineStrategy::Silence,
+            None => app_config.refine_strategy,
+        };
+        let options = chapter_refinement::RefineOptions {
+            strategy,
+            window: app_config.refine_silence_window,
+            noise_db: app_config.refine_noise_db,
+            min_silence: app_config.refine_min_silence,
+            max_delta: cli.refine_max_delta.unwrap_or(app_config.refine_max_delta),
+            min_confidence: cli
+                .refine_min_confidence
+                .unwrap_or(app_config.refine_min_confidence),
+        };
+        log::info!("Refining chapters ({:?})", options);
+        let refinement =
+            chapter_refinement::refine_chapters(&chapters_to_use, &split_source, &options)?;
+        chapter_refinement::print_refinement_report(&refinement);
+        if app_config.refine_manifest {
+            let path = output_dir.join(chapter_refinement::MANIFEST_FILE);
+            if let Err(e) =
+                chapter_refinement::write_manifest(&refinement, &options, &clean_url, &path)
+            {
+                log::warn!("Cannot write {}: {}", path.display(), e);
+            }
+        }
+        chapters_to_use = refinement.chapters;
+    }
+    if _sponsor_free_audio.is_none() {
+        // Same timeline as the download: keep the refined and marked cuts.
+        full_chapters = chapters_to_use.clone();
+    }
+
+    let filter = chapter_filter(cli, app_config)?;
+    if !filter.is_all() {
+        let before = chapters_to_use.len();
+        chapters_to_use = filter.apply(&chapters_to_use)?;
+        declared_chapters = filter.apply(&declared_chapters)?;
         log::info!(
-            "Refining chapters (window={}s noise={}dB min_silence={}s)",
-            app_config.refine_silence_window,
-            app_config.refine_noise_db,
-            app_config.refine_min_silence
+            "Chapter filter kept {} of {} chapters",
+            chapters_to_use.len(),
+            before
         );
-        chapters_to_use = chapter_refinement::refine_chapters_with_silence(
-            &chapters_to_use,
-            &audio_file,
-            app_config.refine_silence_window,
-            app_config.refine_noise_db,
-            app_config.refine_min_silence,
-        )?;
+    }
+
+    if edit_chapters && !reordered {
+        set_status(&format!("editing chapters of \"{}\"", album));
+        // The tracks are cut from `split_source`, which is shorter than the
+        // video once SponsorBlock segments are removed.
+        let split_duration = if _sponsor_free_audio.is_some() {
+            audio::get_audio_duration(&split_source).ok()
+        } else {
+            audio_duration
+        }
+        .unwrap_or(video_info.duration);
+        let edit = || {
+            chapter_review::edit_chapters_in_editor(
+                &chapters_to_use,
+                &video_info.title,
+                split_duration,
+            )
+        };
+        chapters_to_use = match parallel {
+            Some(p) => p.progress.suspend(edit)?,
+            None => edit()?,
+        };
+    }
+
+    if (cli.review || app_config.review_chapters) && !cli.non_interactive {
+        set_status(&format!("reviewing chapters of \"{}\"", album));
+        let review = ChapterReview::new(&declared_chapters, chapters_to_use);
+        chapters_to_use = match parallel {
+            Some(p) => p.progress.suspend(|| ui::prompt_chapter_review(review))?,
+            None => ui::prompt_chapter_review(review)?,
+        };
     }
 
     let cover_path = downloader::album_cover_path(&output_dir);
-    ui::print_splitting_section_header(chapters_to_use.len());
 
-    let extra_date = video_info
-        .upload_date
-        .as_deref()
-        .and_then(utils::upload_date_to_id3_date);
+    let extra_date = video_info.tag_date();
     let extra_genre = video_info.genre.as_deref();
     let extra_comment = video_info
         .webpage_url
         .as_deref()
         .or(Some(clean_url.as_str()));
 
+    if cli.single_file || app_config.output_mode == config::OutputMode::SingleFile {
+        set_status(&format!("writing \"{}\"", album));
+        ui::print_single_file_section_header(chapters_to_use.len());
+        let single_path = single_file_path(app_config, &video_info, &output_dir, &artist, &album);
+        let single = audio::write_chaptered_file(
+            &split_source,
+            &chapters_to_use,
+            &single_path,
+            &album,
+            &artist,
+            &album,
+            if want_cover {
+                cover_path.as_deref()
+            } else {
+                None
+            },
+            app_config.audio_quality,
+            extra_date.as_deref(),
+            extra_genre,
+            extra_comment,
+            app_config.overwrite_existing,
+        )?;
+        ui::print_single_file_complete(single.to_str().unwrap_or("chaptered file"));
+
+        if !cli.skip_download {
+            std::fs::remove_file(&audio_file).ok();
+        }
+        finish_video(parallel, &output_dir);
+        return Ok(batch::VideoOutcome::Done(output_dir));
+    }
+
+    set_status(&format!(
+        "splitting \"{}\" into {} tracks",
+        album,
+        chapters_to_use.len()
+    ));
+    ui::print_splitting_section_header(chapters_to_use.len());
     let output_files = audio::split_audio_by_chapters(
-        &audio_file,
+        &split_source,
         &chapters_to_use,
         &output_dir,
         &artist,
@@ -477,7 +1148,9 @@ fn process_single_video(
         extra_genre,
         extra_comment,
         app_config.overwrite_existing,
-        Some(track_progress_callback),
+        parallel
+            .is_none()
+            .then_some(track_progress_callback as audio::TrackProgressCallback),
     )?;
 
     if app_config.create_playlist {
@@ -488,46 +1161,523 @@ fn process_single_video(
         }
     }
 
+    if app_config.keep_source_audio {
+        let full_path = output_dir.join(format!(
+            "{}.{}",
+            app_config.format_source_audio_name(&artist, &album),
+            ext
+        ));
+        let full = audio::write_chaptered_file(
+            &audio_file,
+            &full_chapters,
+            &full_path,
+            &album,
+            &artist,
+            &album,
+            if want_cover {
+                cover_path.as_deref()
+            } else {
+                None
+            },
+            app_config.audio_quality,
+            extra_date.as_deref(),
+            extra_genre,
+            extra_comment,
+            app_config.overwrite_existing,
+        )?;
+        ui::print_source_audio_kept(full.to_str().unwrap_or("full-length audio"));
+    }
+
     ui::print_splitting_complete();
 
     if !cli.skip_download {
         std::fs::remove_file(&audio_file).ok();
     }
 
-    ui::print_final_result(&output_dir);
+    finish_video(parallel, &output_dir);
 
-    Ok(())
+    Ok(batch::VideoOutcome::Done(output_dir))
+}
+
+/// Processes a playlist as one album: every video is downloaded and becomes a
+/// numbered track of a single folder, sharing one cover.
+fn process_playlist_album(
+    job: &batch::VideoJob,
+    cli: &Cli,
+    app_config: &config::Config,
+    batch: Option<BatchCtx>,
+) -> Result<batch::VideoOutcome> {
+    let forced_artist = job.artist.as_ref().or(cli.artist.as_ref());
+    let forced_album = job.album.as_ref().or(cli.album.as_ref());
+    let parallel = batch.as_ref().and_then(|b| b.parallel.as_ref());
+    let set_status = |msg: &str| {
+        if let Some(p) = parallel {
+            p.line.set_message(msg.to_string());
+        }
+    };
+    let cookies = app_config.cookies_from_browser.as_deref();
+
+    set_status("fetching playlist");
+    ui::print_section_header("Fetching playlist");
+    let mut info = playlist::get_playlist_info(&job.url, cookies)?;
+    info.videos = select_playlist_entries(&info, cli, &playlist_selection(cli)?, parallel)?;
+
+    // YouTube Music album playlists are titled "Album - <name>" and belong to
+    // the artist's Topic channel; other playlists may be "Artist - Album".
+    let ((parsed_artist, parsed_album), _, _) =
+        utils::parse_artist_album_with_source(info.album_title());
+    let album_playlist = playlist::is_album_playlist_id(&info.id);
+    let parsed_album = if album_playlist {
+        utils::clean_folder_name(info.album_title())
+    } else {
+        parsed_album
+    };
+    let (mut artist, mut artist_source) = match forced_artist {
+        Some(a) => (utils::clean_folder_name(a), MetadataSource::Forced),
+        None if job.channel_artist.is_some() => (
+            utils::clean_folder_name(job.channel_artist.as_deref().unwrap_or_default()),
+            MetadataSource::Channel,
+        ),
+        None if album_playlist && info.album_artist().is_some() => (
+            utils::clean_folder_name(info.album_artist().unwrap_or_default()),
+            MetadataSource::Detected,
+        ),
+        None if parsed_artist != "Unknown Artist" => (parsed_artist, MetadataSource::Detected),
+        None if info.album_artist().is_some() => (
+            utils::clean_folder_name(info.album_artist().unwrap_or_default()),
+            MetadataSource::Detected,
+        ),
+        None => ("Unknown Artist".to_string(), MetadataSource::Default),
+    };
+    let mut album = match forced_album {
+        Some(al) => utils::clean_folder_name(al),
+        None => parsed_album,
+    };
+    let mut album_source = if forced_album.is_some() {
+        MetadataSource::Forced
+    } else {
+        MetadataSource::Detected
+    };
+    if artist_source == MetadataSource::Default {
+        if cli.non_interactive {
+            return Err(YtcsError::InputRequired(format!(
+                "Could not determine the artist for playlist \"{}\"; pass --artist, or drop \
+                 --non-interactive.",
+                info.title
+            )));
+        }
+        let (input_artist, input_album) = match parallel {
+            Some(p) => p
+                .progress
+                .suspend(|| ui::prompt_metadata(&info.title, &artist, &album)),
+            None => ui::prompt_metadata(&info.title, &artist, &album),
+        };
+        artist = input_artist;
+        album = input_album;
+        artist_source = MetadataSource::Forced;
+        album_source = MetadataSource::Forced;
+    }
+
+    let total_duration: f64 = info.videos.iter().map(|v| v.duration).sum();
+    ui::print_video_metadata_tree(
+        &info.title,
+        &utils::format_duration(total_duration),
+        info.videos.len(),
+        &artist,
+        &album,
+        artist_source,
+        album_source,
+    );
+
+    let mut folder_name = app_config.format_directory_with_year(
+        &artist,
+        &album,
+        utils::extract_release_year(&info.title, None),
+    );
+    if let Some(b) = &batch
+        && b.total > 1
+        && app_config.playlist_prefix_index
+    {
+        folder_name = format!("{:02} - {}", b.index + 1, folder_name);
+    }
+    let base_output = cli
+        .output
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| app_config.get_output_dir());
+    let output_dir = base_output.join(&folder_name);
+    // Entries landing in the same folder share its scratch files.
+    let _dir_lock = parallel.map(|p| {
+        p.line.set_message("waiting for the output folder");
+        p.dir_locks.lock(&output_dir)
+    });
+
+    let ext = app_config.audio_format.extension();
+    let total = info.videos.len();
+    let track_titles: Vec<String> = info
+        .videos
+        .iter()
+        .map(|v| {
+            v.title
+                .strip_prefix(&format!("{} - ", artist))
+                .unwrap_or(&v.title)
+                .trim()
+                .to_string()
+        })
+        .collect();
+    let track_paths: Vec<PathBuf> = track_titles
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            output_dir.join(format!(
+                "{}.{}",
+                app_config.format_filename(i + 1, &utils::sanitize_title(title), &artist, &album),
+                ext
+            ))
+        })
+        .collect();
+
+    if !app_config.overwrite_existing && track_paths.iter().all(|p| p.exists()) {
+        let reason = format!("all tracks already exist in {}", output_dir.display());
+        match parallel {
+            Some(p) => p.line.finish_with_message(format!("- skipped: {}", reason)),
+            None => ui::print_skipped(&reason),
+        }
+        return Ok(batch::VideoOutcome::Skipped(reason));
+    }
+    std::fs::create_dir_all(&output_dir)?;
+
+    // The first video stands in for the album: its thumbnail is the cover
+    // and its upload date the release date.
+    let first_info = downloader::get_video_info(&info.videos[0].url, cookies)?;
+    let want_cover = app_config.download_cover && !cli.no_cover;
+    if want_cover {
+        match downloader::download_thumbnail_from_info(
+            &first_info,
+            &info.videos[0].url,
+            &output_dir,
+            cookies,
+        ) {
+            Ok(thumb_path) => {
+                ui::print_artwork_saved(thumb_path.to_str().unwrap_or("cover.jpg"));
+            }
+            Err(e) => {
+                log::warn!("Thumbnail download failed: {}", e);
+                ui::print_artwork_failed(
+                    "could not retrieve cover (run with RUST_LOG=warn for details)",
+                );
+            }
+        }
+    } else {
+        ui::print_artwork_disabled();
+    }
+    let cover_path = downloader::album_cover_path(&output_dir).filter(|_| want_cover);
+    let extra_date = first_info.tag_date();
+
+    let download_opts = YtdlpDownloadOpts {
+        non_interactive: cli.non_interactive,
+        ytdlp_auto_update_on_failure: app_config.ytdlp_auto_update && parallel.is_none(),
+        ..YtdlpDownloadOpts::from(app_config)
+    };
+
+    ui::print_splitting_section_header(total);
+    let mut output_files = Vec::with_capacity(total);
+    for (i, video) in info.videos.iter().enumerate() {
+        set_status(&format!("\"{}\" track {}/{}", album, i + 1, total));
+        let temp_audio = output_dir.join(format!("temp_audio_{:03}.{}", i + 1, ext));
+        let audio_file = if cli.skip_download {
+            if !std::fs::metadata(&temp_audio).is_ok_and(|m| m.len() > 0) {
+                return Err(YtcsError::DownloadError(format!(
+                    "--skip-download: expected non-empty file at {}",
+                    temp_audio.display()
+                )));
+            }
+            temp_audio.clone()
+        } else {
+            yt_dlp_progress::download_audio_with_progress(
+                &video.url,
+                &temp_audio,
+                cookies,
+                download_opts.clone(),
+                parallel.map(|p| p.progress.download_bar(&p.line)),
+                None,
+            )?
+        };
+
+        let track = audio::write_album_track(
+            &audio_file,
+            &track_paths[i],
+            i + 1,
+            total,
+            &track_titles[i],
+            &artist,
+            &album,
+            cover_path.as_deref(),
+            extra_date.as_deref(),
+            None,
+            Some(video.url.as_str()),
+            app_config.overwrite_existing,
+        )?;
+        if !cli.skip_download {
+            std::fs::remove_file(&audio_file).ok();
+        }
+        if parallel.is_none() {
+            track_progress_callback(
+                i + 1,
+                total,
+                &track_titles[i],
+                &utils::format_duration_short(video.duration),
+            );
+        }
+        output_files.push(track);
+    }
+
+    if app_config.create_playlist {
+        let m3u = audio::write_m3u_playlist(&output_dir, &output_files)?;
+        if !ui::is_output_quiet() {
+            ui::print_section_header("Playlist");
+            println!("  └─ {}", m3u.display());
+        }
+    }
+
+    ui::print_splitting_complete();
+    finish_video(parallel, &output_dir);
+
+    Ok(batch::VideoOutcome::Done(output_dir))
+}
+
+/// The `--single-file` output, named like the album folder (year included).
+fn single_file_path(
+    app_config: &config::Config,
+    video_info: &downloader::VideoInfo,
+    output_dir: &std::path::Path,
+    artist: &str,
+    album: &str,
+) -> PathBuf {
+    output_dir.join(format!(
+        "{}.{}",
+        app_config.format_directory_with_year(artist, album, video_info.release_year()),
+        app_config.single_file_format.extension()
+    ))
+}
+
+/// Detects output left by a previous run, so a re-run of a batch (e.g. from
+/// the failures list) skips finished videos instead of failing on them.
+/// Only possible before download when the video has YouTube chapters.
+fn existing_output(
+    cli: &Cli,
+    app_config: &config::Config,
+    video_info: &downloader::VideoInfo,
+    output_dir: &std::path::Path,
+    artist: &str,
+    album: &str,
+) -> Option<String> {
+    if cli.single_file || app_config.output_mode == config::OutputMode::SingleFile {
+        let path = single_file_path(app_config, video_info, output_dir, artist, album);
+        return path
+            .exists()
+            .then(|| format!("{} already exists", path.display()));
+    }
+    if video_info.chapters.is_empty() {
+        return None;
+    }
+    let all_tracks_exist = video_info.chapters.iter().enumerate().all(|(i, chapter)| {
+        let name = config::Config::format_filename_with_template(
+            &app_config.filename_format,
+            i + 1,
+            &chapter.sanitize_title(),
+            artist,
+            album,
+        );
+        output_dir
+            .join(format!("{}.{}", name, app_config.audio_format.extension()))
+            .exists()
+    });
+    all_tracks_exist.then(|| format!("all tracks already exist in {}", output_dir.display()))
+}
+
+/// Reports a finished video: on its status line in a parallel batch, otherwise
+/// with the usual final result block.
+fn finish_video(parallel: Option<&ParallelCtx>, output_dir: &std::path::Path) {
+    match parallel {
+        Some(p) => p
+            .line
+            .finish_with_message(format!("✓ {}", output_dir.display())),
+        None => ui::print_final_result(output_dir),
+    }
+}
+
+/// Processes every URL of a multi-video batch, with up to `jobs` videos in
+/// flight. A failing video is recorded and the batch keeps going; the summary
+/// and the failures list are written at the end.
+fn run_batch(
+    jobs: &[batch::VideoJob],
+    unresolved: Vec<(batch::VideoJob, YtcsError)>,
+    workers: usize,
+    cli: &Cli,
+    app_config: &config::Config,
+) -> Result<()> {
+    let total = jobs.len();
+    let results: Vec<Result<batch::VideoOutcome>> = if workers > 1 {
+        run_parallel(jobs, workers, cli, app_config)
+    } else {
+        jobs.iter()
+            .enumerate()
+            .map(|(i, job)| {
+                if i > 0 {
+                    println!();
+                }
+                let ctx = BatchCtx {
+                    index: i,
+                    total,
+                    parallel: None,
+                };
+                let result = process_single_video(job, cli, app_config, Some(ctx));
+                if let Err(e) = &result {
+                    ui::print_error(&e.to_string());
+                }
+                result
+            })
+            .collect()
+    };
+
+    let mut report = batch::BatchReport::default();
+    for (job, e) in unresolved {
+        report.push(&job.to_list_line(), Err(e));
+    }
+    for (job, result) in jobs.iter().zip(results) {
+        report.push(&job.to_list_line(), result);
+    }
+    ui::print_batch_summary(&report);
+
+    if report.failed_count() > 0 {
+        let failures_path = cli.failures_file.clone().unwrap_or_else(|| {
+            cli.output
+                .as_ref()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| app_config.get_output_dir())
+                .join("ytcs-failed.txt")
+        });
+        match report.write_failures_list(&failures_path) {
+            Ok(()) => ui::print_failures_list_written(&failures_path),
+            Err(e) => log::warn!(
+                "Could not write failures list {}: {}",
+                failures_path.display(),
+                e
+            ),
+        }
+    }
+
+    report.into_result()
+}
+
+/// Runs the batch on a worker pool, one status line per video.
+fn run_parallel(
+    jobs: &[batch::VideoJob],
+    workers: usize,
+    cli: &Cli,
+    app_config: &config::Config,
+) -> Vec<Result<batch::VideoOutcome>> {
+    let total = jobs.len();
+    let progress = batch::BatchProgress::new(cli.quiet);
+    let split_slots = batch::Semaphore::new(app_config.parallel_splits);
+    let dir_locks = batch::DirLocks::new();
+    let lines: Vec<_> = (0..total).map(|i| progress.video_line(i, total)).collect();
+
+    // Per-video tree output would interleave between workers; the status
+    // lines replace it for the duration of the batch.
+    ui::set_output_quiet(true);
+    let results = batch::run_pool(total, workers, |i| {
+        let line = lines[i].clone();
+        line.enable_steady_tick(std::time::Duration::from_millis(100));
+        let ctx = BatchCtx {
+            index: i,
+            total,
+            parallel: Some(ParallelCtx {
+                progress: &progress,
+                line: line.clone(),
+                split_slots: &split_slots,
+                dir_locks: &dir_locks,
+            }),
+        };
+        let result = process_single_video(&jobs[i], cli, app_config, Some(ctx));
+        if let Err(e) = &result {
+            line.abandon_with_message(format!("✗ {}", e));
+        }
+        result
+    });
+    ui::set_output_quiet(cli.quiet);
+
+    let results: Vec<_> = results
+        .into_iter()
+        .map(|r| r.unwrap_or_else(|| Err(YtcsError::Other("worker panicked".to_string()))))
+        .collect();
+    if cli.quiet {
+        // Same contract as a sequential run: quiet still prints each output folder.
+        for result in &results {
+            if let Ok(batch::VideoOutcome::Done(dir)) = result {
+                println!("{}", dir.display());
+            }
+        }
+    }
+    results
 }
 
 fn main() {
     if let Err(e) = run() {
         eprintln!("{} {}", "✗".red().bold(), format!("{}", e).red());
-        let exit_code = if matches!(e, YtcsError::InputRequired(_)) {
-            2
-        } else {
-            1
-        };
-        std::process::exit(exit_code);
+        std::process::exit(e.exit_code());
     }
 }
 
 fn run() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
 
-    if let Some(Commands::Config { show }) = &cli.command {
-        if *show {
-            config::print_config_summary()?;
-        } else {
-            config::run_interactive_config_wizard()?;
+    match &cli.command {
+        Some(Commands::Config { show }) => {
+            if *show {
+                config::print_config_summary()?;
+            } else {
+                config::run_interactive_config_wizard()?;
+            }
+            return Ok(());
         }
-        return Ok(());
+        Some(Commands::TestTitle { title }) => return run_test_title(title),
+        None => {}
     }
 
-    let url = cli.url.as_ref().ok_or_else(|| {
-        YtcsError::ConfigError("Missing URL. Usage: ytcs <URL> | ytcs config [--show]".to_string())
-    })?;
+    if cli.batch_file.as_deref() == Some("-") {
+        // stdin is the URL list, so nothing is left to answer a prompt with.
+        if cli.edit_chapters {
+            return Err(YtcsError::InputRequired(
+                "--edit-chapters needs the terminal, but --batch-file - reads the URL list \
+                 from stdin; pass the list as a file instead."
+                    .to_string(),
+            ));
+        }
+        cli.non_interactive = true;
+    }
+    let batch_list = match cli.batch_file.as_deref() {
+        Some(path) => Some(read_batch_list(path)?),
+        None => None,
+    };
+    let mut lines = match batch_list {
+        Some(lines) => lines,
+        None => vec![batch::VideoJob::new(cli.url.as_deref().ok_or_else(
+            || {
+                YtcsError::ConfigError(
+                "Missing URL. Usage: ytcs <URL> | ytcs --batch-file <PATH> | ytcs config [--show]"
+                    .to_string(),
+            )
+            },
+        )?)],
+    };
 
+    let selection = playlist_selection(&cli)?;
     let app_config = config::Config::load()?;
+    chapter_filter(&cli, &app_config)?;
+    check_cli_overrides(&cli, &app_config)?;
+    title_rules::set_active(app_config.title_rules.compile()?);
 
     ui::set_output_quiet(cli.quiet);
 
@@ -537,20 +1687,131 @@ fn run() -> Result<()> {
         handle_missing_dependencies(e, &app_config.dependency_auto_install, cli.non_interactive)?;
     }
 
-    let video_urls = resolve_video_urls(url, &app_config, cli.non_interactive)?;
+    // Each line expands to its playlist entries (per playlist_behavior); a
+    // line that can't be resolved is reported with the batch instead of
+    // aborting the others.
+    let from_list = cli.batch_file.is_some();
+    let mut video_jobs = Vec::new();
+    let mut unresolved = Vec::new();
+    for line in &mut lines {
+        line.as_album |= cli.as_album;
+    }
+    for line in &lines {
+        match expand_line(line, &cli, &app_config, &selection) {
+            Ok(jobs) => video_jobs.extend(jobs),
+            Err(e) if from_list => unresolved.push((line.clone(), e)),
+            Err(e) => return Err(e),
+        }
+    }
 
     if cli.dry_run {
-        return run_dry_run(&video_urls, &cli, &app_config);
+        for (line, e) in &unresolved {
+            ui::print_error(&format!("{}: {}", line.url, e));
+        }
+        return run_dry_run(&video_jobs, &cli, &app_config);
     }
 
-    let n = video_urls.len();
-    for (i, video_url) in video_urls.iter().enumerate() {
-        if i > 0 {
-            println!();
-        }
-        let batch = (n > 1).then_some(BatchCtx { index: i, total: n });
-        process_single_video(video_url, &cli, &app_config, batch)?;
+    if let ([job], []) = (video_jobs.as_slice(), unresolved.as_slice()) {
+        process_single_video(job, &cli, &app_config, None)?;
+        return Ok(());
     }
 
+    let workers = cli.jobs.unwrap_or(app_config.parallel_downloads).max(1);
+    run_batch(&video_jobs, unresolved, workers, &cli, &app_config)
+}
+
+/// `ytcs test-title`: previews the cleanup of one title without downloading.
+fn run_test_title(title: &str) -> Result<()> {
+    let cfg = config::Config::load()?;
+    title_rules::set_active(cfg.title_rules.compile()?);
+
+    let ((artist, album), artist_src, album_src) = utils::parse_artist_album_with_source(title);
+    let year = utils::extract_release_year(title, None);
+    println!("Title       {}", title);
+    println!("  artist    {} ({})", artist, artist_src.label());
+    println!("  album     {} ({})", album, album_src.label());
+    println!(
+        "  folder    {}",
+        cfg.format_directory_with_year(&artist, &album, year)
+    );
+    println!("  track     {}", utils::clean_track_title(title));
+    println!("  file      {}", utils::sanitize_title(title));
     Ok(())
 }
+
+/// Expands one input URL into the jobs to run: a channel Releases/Playlists
+/// tab becomes one album job per playlist, anything else goes through
+/// `resolve_video_urls`.
+fn expand_line(
+    line: &batch::VideoJob,
+    cli: &Cli,
+    cfg: &config::Config,
+    selection: &PlaylistSelection,
+) -> Result<Vec<batch::VideoJob>> {
+    if line.as_album {
+        return Ok(vec![album_job(line)]);
+    }
+    if playlist::is_channel_playlists_url(&line.url) {
+        let channel =
+            playlist::get_channel_playlists(&line.url, cfg.cookies_from_browser.as_deref())?;
+        log::info!(
+            "Channel tab expanded to {} playlists",
+            channel.playlists.len()
+        );
+        return Ok(channel
+            .playlists
+            .iter()
+            .map(|p| batch::VideoJob {
+                as_album: true,
+                // The channel is the artist unless the line says otherwise.
+                channel_artist: (!channel.name.is_empty()).then(|| channel.name.clone()),
+                ..line.with_url(&p.url)
+            })
+            .collect());
+    }
+    if cfg.album_playlists
+        && is_playlist_only_page(&line.url)
+        && playlist::is_playlist_url(&line.url)
+            .is_some_and(|id| playlist::is_album_playlist_id(&id))
+    {
+        log::info!("YouTube Music album playlist; processing as one album");
+        return Ok(vec![album_job(line)]);
+    }
+    let urls = resolve_video_urls(&line.url, cfg, cli, selection)?;
+    Ok(urls.iter().map(|u| line.with_url(u)).collect())
+}
+
+/// Turns a line into a playlist-as-album job, pointing at the playlist page so
+/// a `watch?v=…&list=…` link yields the whole album rather than one video.
+fn album_job(line: &batch::VideoJob) -> batch::VideoJob {
+    let url = match playlist::is_playlist_url(&line.url) {
+        Some(id) => format!("https://www.youtube.com/playlist?list={}", id),
+        None => line.url.clone(),
+    };
+    batch::VideoJob {
+        as_album: true,
+        ..line.with_url(&url)
+    }
+}
+
+/// Reads and parses a `--batch-file` list (`-` = stdin).
+fn read_batch_list(path: &str) -> Result<Vec<batch::VideoJob>> {
+    let text = if path == "-" {
+        let mut text = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)?;
+        text
+    } else {
+        let expanded = shellexpand::tilde(path);
+        std::fs::read_to_string(expanded.as_ref()).map_err(|e| {
+            YtcsError::ConfigError(format!("Cannot read batch file {}: {}", path, e))
+        })?
+    };
+    let jobs = batch::parse_batch_list(&text)?;
+    if jobs.is_empty() {
+        return Err(YtcsError::ConfigError(format!(
+            "Batch file {} contains no URLs",
+            path
+        )));
+    }
+    Ok(jobs)
+}
@@ -0,0 +1,133 @@
+//! Site-agnostic URL identity.
+//!
+//! yt-dlp handles far more than YouTube (SoundCloud, Bandcamp, Vimeo,
+//! Mixcloud, archive.org, …). YouTube URLs are normalized to their canonical
+//! `watch?v=` form; any other web URL is passed through to yt-dlp as is, and
+//! the site is identified afterwards from yt-dlp's JSON (`extractor_key`, `id`).
+
+use crate::downloader::extract_video_id;
+use crate::error::{Result, YtcsError};
+
+/// Which site (yt-dlp extractor) a video comes from, and its id on that site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaId {
+    /// Lowercase yt-dlp extractor key (`youtube`, `soundcloud`, `bandcamp`, …)
+    pub extractor: String,
+
+    /// The video's id on that site
+    pub id: String,
+}
+
+impl MediaId {
+    /// Reads the extractor and id from a yt-dlp `--dump-json` object.
+    ///
+    /// Returns `None` when yt-dlp reported no id.
+    pub fn from_ytdlp_json(data: &serde_json::Value) -> Option<Self> {
+        let id = data["id"].as_str().filter(|s| !s.is_empty())?;
+        let extractor = data["extractor_key"]
+            .as_str()
+            .or_else(|| data["extractor"].as_str())
+            .unwrap_or("generic")
+            .to_lowercase();
+        Some(Self {
+            extractor,
+            id: id.to_string(),
+        })
+    }
+
+    /// Returns true for YouTube (including YouTube Music) videos.
+    pub fn is_youtube(&self) -> bool {
+        self.extractor.starts_with("youtube")
+    }
+}
+
+/// Returns true if the URL points at YouTube or YouTube Music.
+pub fn is_youtube_url(url: &str) -> bool {
+    host(url).is_some_and(|h| {
+        let h = h.strip_prefix("www.").unwrap_or(&h);
+        h == "youtu.be" || h == "youtube.com" || h.ends_with(".youtube.com")
+    })
+}
+
+/// Normalizes a URL given on the command line or in a batch list.
+///
+/// YouTube links become `https://www.youtube.com/watch?v=<id>`; other
+/// `http(s)` URLs are returned unchanged (minus any `#fragment`) for yt-dlp
+/// to resolve.
+///
+/// # Errors
+///
+/// Returns an `InvalidUrl` error for a YouTube link without a video id, or
+/// for anything that isn't an `http(s)` URL
+pub fn canonical_media_url(url: &str) -> Result<String> {
+    let url = url.trim();
+    if is_youtube_url(url) {
+        let id = extract_video_id(url)?;
+        return Ok(format!("https://www.youtube.com/watch?v={}", id));
+    }
+    if host(url).is_some() {
+        return Ok(url.split('#').next().unwrap_or(url).to_string());
+    }
+    Err(YtcsError::InvalidUrl(format!(
+        "Unable to extract a video ID or web URL from: {}",
+        url
+    )))
+}
+
+/// Lowercase host of an `http(s)` URL, if it has one.
+fn host(url: &str) -> Option<String> {
+    let lower = url.trim().to_lowercase();
+    let rest = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))?;
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit('@').next()?.split(':').next()?;
+    (host.contains('.') && !host.starts_with('.')).then(|| host.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_youtube_url() {
+        assert!(is_youtube_url(
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        ));
+        assert!(is_youtube_url(
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ"
+        ));
+        assert!(is_youtube_url("https://youtu.be/dQw4w9WgXcQ"));
+        assert!(!is_youtube_url("https://soundcloud.com/artist/set"));
+        assert!(!is_youtube_url(
+            "https://notyoutube.com/watch?v=dQw4w9WgXcQ"
+        ));
+    }
+
+    #[test]
+    fn test_canonical_media_url() {
+        assert_eq!(
+            canonical_media_url("https://youtu.be/dQw4w9WgXcQ?t=42").unwrap(),
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+        assert_eq!(
+            canonical_media_url("https://artist.bandcamp.com/album/live-set#t1").unwrap(),
+            "https://artist.bandcamp.com/album/live-set"
+        );
+        assert!(canonical_media_url("not-a-url").is_err());
+        assert!(canonical_media_url("https://www.youtube.com/").is_err());
+        assert!(canonical_media_url("ftp://archive.org/file").is_err());
+    }
+
+    #[test]
+    fn test_media_id_from_ytdlp_json() {
+        let data = serde_json::json!({"id": "123456", "extractor_key": "SoundcloudSet"});
+        let media = MediaId::from_ytdlp_json(&data).unwrap();
+        assert_eq!(media.extractor, "soundcloudset");
+        assert!(!media.is_youtube());
+
+        let data = serde_json::json!({"id": "dQw4w9WgXcQ", "extractor": "youtube"});
+        assert!(MediaId::from_ytdlp_json(&data).unwrap().is_youtube());
+        assert!(MediaId::from_ytdlp_json(&serde_json::json!({})).is_none());
+    }
+}
@@ -3,6 +3,10 @@
 //! This module detects if a URL is a playlist and extracts videos.
 //!
 //! Playlist handling is driven by config `playlist_behavior` (`VideoOnly`, `Ask`, `PlaylistOnly`).
+//!
+//! A channel's Releases / Playlists tab is a list of playlists rather than of
+//! videos; [`get_channel_playlists`] enumerates it so each playlist can be
+//! processed as one album.
 
 use crate::cookie_helper;
 use crate::error::{Result, YtcsError};
@@ -24,6 +28,10 @@ pub struct PlaylistVideo {
 
     /// Duration in seconds
     pub duration: f64,
+
+    /// Channel that uploaded the video (`Artist - Topic` for YouTube Music tracks)
+    #[serde(default)]
+    pub channel: Option<String>,
 }
 
 /// Information about a playlist
@@ -35,10 +43,105 @@ pub struct PlaylistInfo {
     /// Playlist title
     pub title: String,
 
+    /// Channel that owns the playlist, when yt-dlp reports it
+    pub uploader: Option<String>,
+
     /// List of videos
     pub videos: Vec<PlaylistVideo>,
 }
 
+impl PlaylistInfo {
+    /// Album name for playlist-as-album mode: the title without the `Album - `
+    /// prefix YouTube Music puts on album playlists.
+    pub fn album_title(&self) -> &str {
+        let title = self.title.trim();
+        title
+            .strip_prefix("Album - ")
+            .or_else(|| title.strip_prefix("Album – "))
+            .unwrap_or(title)
+            .trim()
+    }
+
+    /// Artist for playlist-as-album mode: the auto-generated `… - Topic`
+    /// channel of the tracks when there is one (that's the performing artist,
+    /// not whoever curated the playlist), otherwise the playlist owner.
+    pub fn album_artist(&self) -> Option<&str> {
+        self.videos
+            .iter()
+            .filter_map(|v| v.channel.as_deref())
+            .find(|c| c.trim().ends_with(" - Topic"))
+            .or(self.uploader.as_deref())
+            .or_else(|| self.videos.iter().find_map(|v| v.channel.as_deref()))
+            .map(strip_topic_suffix)
+            .filter(|a| !a.is_empty())
+    }
+}
+
+/// Returns true for YouTube Music album playlist ids (`OLAK5uy_…`), whose
+/// videos are the album's songs.
+pub fn is_album_playlist_id(id: &str) -> bool {
+    id.starts_with("OLAK5uy_")
+}
+
+/// A playlist listed on a channel's Releases or Playlists tab
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelPlaylist {
+    /// Playlist ID (`OLAK5uy_…` for releases)
+    pub id: String,
+
+    /// Playlist title (usually the album name)
+    pub title: String,
+
+    /// Playlist URL
+    pub url: String,
+}
+
+/// The playlists of a channel tab
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelInfo {
+    /// Channel name, without the ` - Topic` suffix of auto-generated music channels
+    pub name: String,
+
+    /// Playlists in tab order
+    pub playlists: Vec<ChannelPlaylist>,
+}
+
+/// Returns true for a channel tab that lists playlists: `/@handle/releases`,
+/// `/@handle/playlists`, `/channel/<id>/releases|playlists` (also `/c/` and
+/// `/user/` channel paths).
+///
+/// A bare channel URL (its Videos tab) is not matched.
+///
+/// # Examples
+///
+/// ```
+/// use youtube_chapter_splitter::playlist::is_channel_playlists_url;
+///
+/// assert!(is_channel_playlists_url("https://www.youtube.com/@marigold/releases"));
+/// assert!(is_channel_playlists_url("https://www.youtube.com/channel/UC123/playlists"));
+/// assert!(!is_channel_playlists_url("https://www.youtube.com/@marigold"));
+/// ```
+pub fn is_channel_playlists_url(url: &str) -> bool {
+    let lower = url.to_lowercase();
+    let path = lower
+        .split_once("youtube.com/")
+        .map(|(_, rest)| rest)
+        .unwrap_or("");
+    let path = path.split(['?', '#']).next().unwrap_or("");
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let tab_index = match segments.first() {
+        Some(first) if first.starts_with('@') => 1,
+        Some(&"channel") | Some(&"c") | Some(&"user") => 2,
+        _ => return false,
+    };
+    segments.len() == tab_index + 1 && matches!(segments[tab_index], "releases" | "playlists")
+}
+
+/// Strips the ` - Topic` suffix YouTube adds to auto-generated artist channels.
+pub fn strip_topic_suffix(channel: &str) -> &str {
+    channel.trim().trim_end_matches(" - Topic").trim_end()
+}
+
 /// Returns the playlist `list` id when the URL references a YouTube playlist.
 ///
 /// Detects `list=` on watch URLs and `/playlist?list=` playlist-only URLs.
@@ -80,40 +183,22 @@ fn extract_list_query_param(url: &str) -> Option<String> {
 ///
 /// Returns an error if yt-dlp fails or if JSON parsing fails
 pub fn get_playlist_info(url: &str, cookies_from_browser: Option<&str>) -> Result<PlaylistInfo> {
-    // Use yt-dlp to get playlist information
-    let mut cmd = Command::new("yt-dlp");
-    cmd.args(["--dump-json", "--flat-playlist", "--no-warnings"]);
-
-    crate::ytdlp_helper::add_ejs_args(&mut cmd);
-    cookie_helper::add_cookie_args(&mut cmd, cookies_from_browser);
-
-    let output = cmd
-        .arg(url)
-        .output()
-        .map_err(|e| YtcsError::DownloadError(format!("Failed to run yt-dlp: {}", e)))?;
-
-    if !output.status.success() {
-        let raw_error = String::from_utf8_lossy(&output.stderr);
-        let (error_msg, suggestion) =
-            ytdlp_error_parser::parse_ytdlp_error(&raw_error, cookies_from_browser);
-
-        let full_error = if let Some(sug) = suggestion {
-            format!("{}\n\n{}", error_msg, sug)
-        } else {
-            error_msg
-        };
-
-        return Err(YtcsError::DownloadError(full_error));
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = run_flat_playlist(url, cookies_from_browser)?;
+    parse_playlist_info(&stdout)
+}
 
-    // Parse each JSON line (one video per line)
+/// Parses the flat-playlist JSON lines of a playlist (one video per line).
+///
+/// # Errors
+///
+/// Returns an error if JSON parsing fails or if no video is listed
+pub fn parse_playlist_info(json_lines: &str) -> Result<PlaylistInfo> {
     let mut videos = Vec::new();
     let mut playlist_title = String::new();
     let mut playlist_id = String::new();
+    let mut uploader = None;
 
-    for line in stdout.lines() {
+    for line in json_lines.lines() {
         if line.trim().is_empty() {
             continue;
         }
@@ -132,6 +217,12 @@ pub fn get_playlist_info(url: &str, cookies_from_browser: Option<&str>) -> Resul
                 playlist_id = id.to_string();
             }
         }
+        if uploader.is_none() {
+            uploader = ["playlist_uploader", "playlist_channel"]
+                .iter()
+                .find_map(|key| json.get(*key).and_then(|v| v.as_str()))
+                .map(str::to_string);
+        }
 
         // Extract video information
         if let Some(id) = json.get("id").and_then(|v| v.as_str()) {
@@ -145,11 +236,17 @@ pub fn get_playlist_info(url: &str, cookies_from_browser: Option<&str>) -> Resul
 
             let url = format!("https://www.youtube.com/watch?v={}", id);
 
+            let channel = ["channel", "uploader"]
+                .iter()
+                .find_map(|key| json.get(*key).and_then(|v| v.as_str()))
+                .map(str::to_string);
+
             videos.push(PlaylistVideo {
                 id: id.to_string(),
                 title,
                 url,
                 duration,
+                channel,
             });
         }
     }
@@ -161,10 +258,112 @@ pub fn get_playlist_info(url: &str, cookies_from_browser: Option<&str>) -> Resul
     Ok(PlaylistInfo {
         id: playlist_id,
         title: playlist_title,
+        uploader,
         videos,
     })
 }
 
+/// Runs `yt-dlp --dump-json --flat-playlist` on `url` and returns its stdout
+/// (one JSON object per entry).
+fn run_flat_playlist(url: &str, cookies_from_browser: Option<&str>) -> Result<String> {
+    let mut cmd = Command::new("yt-dlp");
+    cmd.args(["--dump-json", "--flat-playlist", "--no-warnings"]);
+
+    crate::ytdlp_helper::add_ejs_args(&mut cmd);
+    cookie_helper::add_cookie_args(&mut cmd, cookies_from_browser);
+
+    let output = cmd
+        .arg(url)
+        .output()
+        .map_err(|e| YtcsError::DownloadError(format!("Failed to run yt-dlp: {}", e)))?;
+
+    if !output.status.success() {
+        let raw_error = String::from_utf8_lossy(&output.stderr);
+        let (error_msg, suggestion) =
+            ytdlp_error_parser::parse_ytdlp_error(&raw_error, cookies_from_browser);
+
+        let full_error = if let Some(sug) = suggestion {
+            format!("{}\n\n{}", error_msg, sug)
+        } else {
+            error_msg
+        };
+
+        return Err(YtcsError::DownloadError(full_error));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Lists the playlists of a channel's Releases / Playlists tab
+/// (see [`is_channel_playlists_url`]).
+///
+/// # Errors
+///
+/// Returns an error if yt-dlp fails, if JSON parsing fails, or if the tab
+/// contains no playlists
+pub fn get_channel_playlists(url: &str, cookies_from_browser: Option<&str>) -> Result<ChannelInfo> {
+    let stdout = run_flat_playlist(url, cookies_from_browser)?;
+    parse_channel_playlists(&stdout)
+}
+
+/// Parses the flat-playlist JSON lines of a channel tab. Entries that are not
+/// playlists (e.g. a video shelf on the same tab) are ignored.
+pub fn parse_channel_playlists(json_lines: &str) -> Result<ChannelInfo> {
+    let mut name = String::new();
+    let mut playlists = Vec::new();
+
+    for line in json_lines.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let json: serde_json::Value = serde_json::from_str(line).map_err(YtcsError::JsonError)?;
+
+        if name.is_empty() {
+            for key in [
+                "playlist_channel",
+                "playlist_uploader",
+                "channel",
+                "uploader",
+            ] {
+                if let Some(channel) = json.get(key).and_then(|v| v.as_str()) {
+                    name = strip_topic_suffix(channel).to_string();
+                    break;
+                }
+            }
+        }
+
+        let entry_url = json.get("url").and_then(|v| v.as_str()).unwrap_or("");
+        let Some(id) = is_playlist_url(entry_url).or_else(|| {
+            json.get("id")
+                .and_then(|v| v.as_str())
+                .filter(|id| id.starts_with("OLAK5uy_") || id.starts_with("PL"))
+                .map(str::to_string)
+        }) else {
+            continue;
+        };
+
+        let title = json
+            .get("title")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown")
+            .to_string();
+
+        playlists.push(ChannelPlaylist {
+            url: format!("https://www.youtube.com/playlist?list={}", id),
+            id,
+            title,
+        });
+    }
+
+    if playlists.is_empty() {
+        return Err(YtcsError::Other(
+            "No playlists found on this channel tab".to_string(),
+        ));
+    }
+
+    Ok(ChannelInfo { name, playlists })
+}
+
 /// Remove playlist parameter from a URL
 ///
 /// # Arguments
@@ -258,6 +457,74 @@ mod tests {
         assert!(extract_video_id("https://www.youtube.com/").is_err());
     }
 
+    #[test]
+    fn test_is_channel_playlists_url() {
+        assert!(is_channel_playlists_url(
+            "https://www.youtube.com/@SomeBand/releases"
+        ));
+        assert!(is_channel_playlists_url(
+            "https://youtube.com/@SomeBand/playlists?view=1"
+        ));
+        assert!(is_channel_playlists_url(
+            "https://www.youtube.com/channel/UCabc123/releases/"
+        ));
+        assert!(is_channel_playlists_url(
+            "https://www.youtube.com/c/SomeBand/playlists"
+        ));
+        assert!(!is_channel_playlists_url(
+            "https://www.youtube.com/@SomeBand"
+        ));
+        assert!(!is_channel_playlists_url(
+            "https://www.youtube.com/@SomeBand/videos"
+        ));
+        assert!(!is_channel_playlists_url(
+            "https://www.youtube.com/playlist?list=PLabc"
+        ));
+        assert!(!is_channel_playlists_url(
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        ));
+    }
+
+    #[test]
+    fn test_parse_channel_playlists() {
+        let json = r#"{"_type": "url", "id": "OLAK5uy_first", "url": "https://www.youtube.com/playlist?list=OLAK5uy_first", "title": "First Album", "playlist_channel": "Some Band - Topic"}
+{"_type": "url", "id": "dQw4w9WgXcQ", "url": "https://www.youtube.com/watch?v=dQw4w9WgXcQ", "title": "A video"}
+{"_type": "url", "id": "OLAK5uy_second", "title": "Second Album"}
+"#;
+        let info = parse_channel_playlists(json).unwrap();
+        assert_eq!(info.name, "Some Band");
+        assert_eq!(info.playlists.len(), 2);
+        assert_eq!(info.playlists[0].title, "First Album");
+        assert_eq!(
+            info.playlists[1].url,
+            "https://www.youtube.com/playlist?list=OLAK5uy_second"
+        );
+        assert!(parse_channel_playlists("").is_err());
+    }
+
+    #[test]
+    fn test_parse_album_playlist() {
+        let json = r#"{"id": "aaaaaaaaaaa", "title": "Opening", "duration": 201.0, "channel": "Some Band - Topic", "playlist_title": "Album - First Light", "playlist_id": "OLAK5uy_abc", "playlist_uploader": "Label Records"}
+{"id": "bbbbbbbbbbb", "title": "Second", "duration": 180.5, "channel": "Some Band - Topic"}
+"#;
+        let info = parse_playlist_info(json).unwrap();
+        assert_eq!(info.id, "OLAK5uy_abc");
+        assert!(is_album_playlist_id(&info.id));
+        assert_eq!(info.album_title(), "First Light");
+        assert_eq!(info.album_artist(), Some("Some Band"));
+        assert_eq!(info.videos.len(), 2);
+        assert_eq!(info.videos[1].duration, 180.5);
+    }
+
+    #[test]
+    fn test_album_artist_falls_back_to_playlist_owner() {
+        let json = r#"{"id": "aaaaaaaaaaa", "title": "Song", "channel": "Someone", "playlist_title": "Mix", "playlist_uploader": "Curator"}"#;
+        let info = parse_playlist_info(json).unwrap();
+        assert_eq!(info.album_artist(), Some("Curator"));
+        assert_eq!(info.album_title(), "Mix");
+        assert!(!is_album_playlist_id("PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf"));
+    }
+
     #[test]
     fn test_remove_playlist_param() {
         let url =
@@ -0,0 +1,184 @@
+//! Choosing which playlist entries to process.
+//!
+//! A [`PlaylistSelection`] is applied to the videos returned by
+//! [`crate::playlist::get_playlist_info`]: index ranges (`1-5,8,10-`), title
+//! include/exclude patterns, a duration window and an optional reversal.
+
+use crate::chapters::parse_timestamp;
+use crate::error::{Result, YtcsError};
+use crate::playlist::PlaylistVideo;
+use regex::Regex;
+
+/// An inclusive, 1-based range of playlist positions; `end = None` means
+/// "to the end of the playlist".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemRange {
+    pub start: usize,
+    pub end: Option<usize>,
+}
+
+impl ItemRange {
+    /// Returns true if the 1-based `position` falls inside the range.
+    pub fn contains(&self, position: usize) -> bool {
+        position >= self.start && self.end.is_none_or(|end| position <= end)
+    }
+}
+
+/// Parses an item spec such as `1-5,8,10-` (or `-3` for the first three).
+///
+/// # Errors
+///
+/// Returns a `ConfigError` for a malformed or zero position, or a range whose
+/// end is before its start
+pub fn parse_item_ranges(spec: &str) -> Result<Vec<ItemRange>> {
+    let invalid = |part: &str| {
+        YtcsError::ConfigError(format!(
+            "Invalid playlist item \"{}\" (expected e.g. 1-5,8,10-)",
+            part
+        ))
+    };
+    let position = |s: &str, part: &str| -> Result<usize> {
+        s.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| invalid(part))
+    };
+
+    let mut ranges = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let range = match part.split_once('-') {
+            None => {
+                let n = position(part, part)?;
+                ItemRange {
+                    start: n,
+                    end: Some(n),
+                }
+            }
+            Some((start, end)) => {
+                let start = if start.trim().is_empty() {
+                    1
+                } else {
+                    position(start, part)?
+                };
+                let end = if end.trim().is_empty() {
+                    None
+                } else {
+                    Some(position(end, part)?)
+                };
+                if end.is_some_and(|e| e < start) {
+                    return Err(invalid(part));
+                }
+                ItemRange { start, end }
+            }
+        };
+        ranges.push(range);
+    }
+    if ranges.is_empty() {
+        return Err(invalid(spec));
+    }
+    Ok(ranges)
+}
+
+/// Parses a duration bound given as seconds or `m:ss` / `h:mm:ss`.
+///
+/// # Errors
+///
+/// Returns a `ConfigError` if the value is not a valid timestamp
+pub fn parse_duration_limit(value: &str) -> Result<f64> {
+    parse_timestamp(value.trim()).map_err(|_| {
+        YtcsError::ConfigError(format!(
+            "Invalid duration \"{}\" (expected seconds, m:ss or h:mm:ss)",
+            value
+        ))
+    })
+}
+
+/// Compiles a title filter; matching is case-insensitive.
+///
+/// # Errors
+///
+/// Returns a `ConfigError` if the pattern is not a valid regex
+pub fn title_pattern(pattern: &str) -> Result<Regex> {
+    regex::RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| {
+            YtcsError::ConfigError(format!("Invalid title pattern \"{}\": {}", pattern, e))
+        })
+}
+
+/// Which entries of a playlist to keep, and in which order.
+///
+/// The default selection keeps every entry in playlist order.
+#[derive(Debug, Clone, Default)]
+pub struct PlaylistSelection {
+    /// Positions to keep (`None` = all)
+    pub items: Option<Vec<ItemRange>>,
+
+    /// Keep only titles matching this pattern
+    pub match_title: Option<Regex>,
+
+    /// Drop titles matching this pattern
+    pub reject_title: Option<Regex>,
+
+    /// Drop entries shorter than this many seconds
+    pub min_duration: Option<f64>,
+
+    /// Drop entries longer than this many seconds
+    pub max_duration: Option<f64>,
+
+    /// Process the kept entries last to first
+    pub reverse: bool,
+}
+
+impl PlaylistSelection {
+    /// Returns true if the selection would keep every entry unchanged.
+    pub fn is_all(&self) -> bool {
+        self.items.is_none()
+            && self.match_title.is_none()
+            && self.reject_title.is_none()
+            && self.min_duration.is_none()
+            && self.max_duration.is_none()
+            && !self.reverse
+    }
+
+    /// Filters `videos` (in playlist order) and applies the ordering.
+    ///
+    /// Positions refer to the full playlist, before any other filter. Entries
+    /// whose duration is unknown (reported as 0) are never dropped by the
+    /// duration window.
+    pub fn apply(&self, videos: &[PlaylistVideo]) -> Vec<PlaylistVideo> {
+        let mut kept: Vec<PlaylistVideo> = videos
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                self.items
+                    .as_ref()
+                    .is_none_or(|ranges| ranges.iter().any(|r| r.contains(i + 1)))
+            })
+            .map(|(_, v)| v)
+            .filter(|v| {
+                self.match_title
+                    .as_ref()
+                    .is_none_or(|re| re.is_match(&v.title))
+            })
+            .filter(|v| {
+                !self
+                    .reject_title
+                    .as_ref()
+                    .is_some_and(|re| re.is_match(&v.title))
+            })
+            .filter(|v| {
+                v.duration <= 0.0
+                    || (self.min_duration.is_none_or(|min| v.duration >= min)
+                        && self.max_duration.is_none_or(|max| v.duration <= max))
+            })
+            .cloned()
+            .collect();
+        if self.reverse {
+            kept.reverse();
+        }
+        kept
+    }
+}
@@ -0,0 +1,215 @@
+//! SponsorBlock segments: sponsor reads, "subscribe" reminders, non-music parts.
+//!
+//! Segments are fetched from a SponsorBlock API server (the public one by
+//! default; `sponsorblock_api` points elsewhere, e.g. a local mirror) for a
+//! YouTube video id. They are then either cut out of the audio, with the
+//! chapter times shifted to match ([`remove_segments`]), or turned into
+//! chapters of their own so they can be reviewed or excluded ([`mark_segments`]).
+
+use crate::chapters::Chapter;
+use crate::error::{Result, YtcsError};
+use serde::Deserialize;
+
+/// The public SponsorBlock API server.
+pub const DEFAULT_API: &str = "https://sponsor.ajay.app";
+
+/// HTTP timeout for the segment lookup.
+const API_TIMEOUT_SECS: u64 = 10;
+
+/// Pieces shorter than this (seconds) are not worth a chapter of their own.
+const MIN_PIECE: f64 = 1.0;
+
+/// Categories fetched when the config doesn't list any.
+pub fn default_categories() -> Vec<String> {
+    ["sponsor", "selfpromo", "interaction", "music_offtopic"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+/// Readable name of a SponsorBlock category, used to title marked segments.
+pub fn category_label(category: &str) -> &str {
+    match category {
+        "sponsor" => "Sponsor",
+        "selfpromo" => "Self-promotion",
+        "interaction" => "Interaction reminder",
+        "intro" => "Intro",
+        "outro" => "Outro",
+        "preview" => "Preview",
+        "music_offtopic" => "Non-music",
+        "filler" => "Filler",
+        other => other,
+    }
+}
+
+/// A time range reported by SponsorBlock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub category: String,
+}
+
+impl Segment {
+    /// Length of the segment in seconds.
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiSegment {
+    segment: [f64; 2],
+    #[serde(default)]
+    category: String,
+    #[serde(default, rename = "actionType")]
+    action_type: Option<String>,
+}
+
+/// Parses a `/api/skipSegments` response.
+///
+/// Only `skip` segments are kept (not `poi`/`chapter`/`full` entries); the
+/// result is sorted, and overlapping segments are merged (the first one's
+/// category wins).
+///
+/// # Errors
+///
+/// Returns a `JsonError` if the response isn't a segment list
+pub fn parse_segments(json: &str) -> Result<Vec<Segment>> {
+    let raw: Vec<ApiSegment> = serde_json::from_str(json)?;
+    let mut segments: Vec<Segment> = raw
+        .into_iter()
+        .filter(|s| s.action_type.as_deref().is_none_or(|a| a == "skip"))
+        .map(|s| Segment {
+            start: s.segment[0].max(0.0),
+            end: s.segment[1],
+            category: s.category,
+        })
+        .filter(|s| s.start.is_finite() && s.end.is_finite() && s.end > s.start)
+        .collect();
+    segments.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut merged: Vec<Segment> = Vec::new();
+    for segment in segments {
+        match merged.last_mut() {
+            Some(last) if segment.start <= last.end => last.end = last.end.max(segment.end),
+            _ => merged.push(segment),
+        }
+    }
+    Ok(merged)
+}
+
+/// Fetches the segments of a YouTube video from `api_base`.
+///
+/// A video nobody submitted segments for (HTTP 404) yields an empty list.
+///
+/// # Errors
+///
+/// Returns a `DownloadError` if the server can't be reached or answers with
+/// another error, or a `JsonError` for an unreadable response
+pub fn fetch_segments(
+    api_base: &str,
+    video_id: &str,
+    categories: &[String],
+) -> Result<Vec<Segment>> {
+    let url = format!("{}/api/skipSegments", api_base.trim_end_matches('/'));
+    let categories = serde_json::to_string(categories)?;
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(API_TIMEOUT_SECS))
+        .build();
+    log::debug!(
+        "Fetching SponsorBlock segments for {} from {}",
+        video_id,
+        url
+    );
+
+    match agent
+        .get(&url)
+        .query("videoID", video_id)
+        .query("categories", &categories)
+        .call()
+    {
+        Ok(response) => {
+            let body = response.into_string().map_err(|e| {
+                YtcsError::DownloadError(format!("SponsorBlock: reading response failed: {}", e))
+            })?;
+            parse_segments(&body)
+        }
+        Err(ureq::Error::Status(404, _)) => Ok(Vec::new()),
+        Err(ureq::Error::Status(code, _)) => Err(YtcsError::DownloadError(format!(
+            "SponsorBlock: HTTP {} from {}",
+            code, url
+        ))),
+        Err(e) => Err(YtcsError::DownloadError(format!("SponsorBlock: {}", e))),
+    }
+}
+
+/// Position of `t` once the `segments` (sorted, non-overlapping) are cut out.
+fn position_after_cuts(t: f64, segments: &[Segment]) -> f64 {
+    let removed: f64 = segments
+        .iter()
+        .take_while(|s| s.start < t)
+        .map(|s| s.end.min(t) - s.start)
+        .sum();
+    t - removed
+}
+
+/// Chapter times once `segments` have been cut out of the audio.
+///
+/// Every time is moved earlier by the length of the segments before it;
+/// a chapter lying entirely inside a segment disappears.
+pub fn remove_segments(chapters: &[Chapter], segments: &[Segment]) -> Vec<Chapter> {
+    chapters
+        .iter()
+        .filter_map(|chapter| {
+            let start = position_after_cuts(chapter.start_time, segments);
+            let end = position_after_cuts(chapter.end_time, segments);
+            if end - start < MIN_PIECE {
+                log::info!(
+                    "Chapter \"{}\" is entirely SponsorBlock segments; dropped",
+                    chapter.title
+                );
+                return None;
+            }
+            Chapter::try_new(chapter.title.clone(), start, end)
+                .inspect_err(|e| log::warn!("{}; dropped", e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Splits chapters at segment edges so each segment becomes a chapter of its
+/// own, titled after its category (`[Sponsor]`, `[Non-music]`, …).
+///
+/// Edges closer than a second to an existing cut are ignored.
+pub fn mark_segments(chapters: &[Chapter], segments: &[Segment]) -> Vec<Chapter> {
+    let mut marked = Vec::new();
+    for chapter in chapters {
+        let mut cuts = vec![chapter.start_time];
+        for edge in segments.iter().flat_map(|s| [s.start, s.end]) {
+            if edge - chapter.start_time >= MIN_PIECE
+                && chapter.end_time - edge >= MIN_PIECE
+                && cuts.last().is_none_or(|last| edge - last >= MIN_PIECE)
+            {
+                cuts.push(edge);
+            }
+        }
+        cuts.push(chapter.end_time);
+
+        for piece in cuts.windows(2) {
+            let middle = (piece[0] + piece[1]) / 2.0;
+            let title = match segments
+                .iter()
+                .find(|s| s.start <= middle && middle < s.end)
+            {
+                Some(segment) => format!("[{}]", category_label(&segment.category)),
+                None => chapter.title.clone(),
+            };
+            match Chapter::try_new(title, piece[0], piece[1]) {
+                Ok(piece) => marked.push(piece),
+                Err(e) => log::warn!("{}; dropped", e),
+            }
+        }
+    }
+    marked
+}
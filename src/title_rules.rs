@@ -0,0 +1,260 @@
+//! User-defined title cleanup rules.
+//!
+//! The `[title_rules]` table in `config.toml` adds regex replacements, strip
+//! patterns, a case style and a small-words list on top of the built-in
+//! cleanup in [`crate::utils`]. The rules apply to artist and album names
+//! (folder names and tags), track file names and track title tags alike.
+//!
+//! ```toml
+//! [title_rules]
+//! case = "title"
+//! small_words = ["a", "an", "and", "of", "the"]
+//! strip = ['\s*\(Remaster(ed)?( \d{4})?\)']
+//!
+//! [[title_rules.replace]]
+//! pattern = '\bfeat\.'
+//! with = "ft."
+//! ```
+
+use crate::error::{Result, YtcsError};
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+/// How letters are cased after the other rules ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseStyle {
+    /// Leave the casing as it is
+    Keep,
+    /// First letter of every word uppercase, the rest lowercase
+    Title,
+    /// First letter uppercase, everything else lowercase
+    Sentence,
+    /// Everything lowercase
+    Lower,
+}
+
+/// One regex find/replace step (`$1`-style group references allowed).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplaceRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub with: String,
+}
+
+/// The `[title_rules]` section of the config, as written by the user.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TitleRules {
+    /// Find/replace steps, applied in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replace: Vec<ReplaceRule>,
+
+    /// Patterns removed after the replacements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip: Vec<String>,
+
+    /// Case style; unset keeps the built-in behavior (title case for folder
+    /// and file names, track title tags as published)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case: Option<CaseStyle>,
+
+    /// Words kept lowercase by title case unless first or last (`of`, `the`, …)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub small_words: Vec<String>,
+}
+
+impl TitleRules {
+    /// Returns true if no rule is configured.
+    pub fn is_empty(&self) -> bool {
+        self.replace.is_empty()
+            && self.strip.is_empty()
+            && self.case.is_none()
+            && self.small_words.is_empty()
+    }
+
+    /// Compiles the patterns (case-insensitive).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` naming the first invalid pattern
+    pub fn compile(&self) -> Result<TitleRuleSet> {
+        let build = |pattern: &str| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| {
+                    YtcsError::ConfigError(format!(
+                        "title_rules: invalid pattern {:?}: {}",
+                        pattern, e
+                    ))
+                })
+        };
+        Ok(TitleRuleSet {
+            replace: self
+                .replace
+                .iter()
+                .map(|r| Ok((build(&r.pattern)?, r.with.clone())))
+                .collect::<Result<_>>()?,
+            strip: self.strip.iter().map(|p| build(p)).collect::<Result<_>>()?,
+            case: self.case,
+            small_words: self
+                .small_words
+                .iter()
+                .map(|w| w.trim().to_lowercase())
+                .filter(|w| !w.is_empty())
+                .collect(),
+        })
+    }
+}
+
+/// Compiled [`TitleRules`], ready to apply.
+#[derive(Debug, Clone, Default)]
+pub struct TitleRuleSet {
+    replace: Vec<(Regex, String)>,
+    strip: Vec<Regex>,
+    case: Option<CaseStyle>,
+    small_words: HashSet<String>,
+}
+
+impl TitleRuleSet {
+    /// Applies the replacements, strip patterns and case style to `text`.
+    ///
+    /// `default_case` is used when the rules leave `case` unset; it is how the
+    /// caller cased its output before rules existed.
+    pub fn apply(&self, text: &str, default_case: CaseStyle) -> String {
+        let mut out = text.to_string();
+        for (re, with) in &self.replace {
+            out = re.replace_all(&out, with.as_str()).into_owned();
+        }
+        for re in &self.strip {
+            out = re.replace_all(&out, "").into_owned();
+        }
+        if !self.replace.is_empty() || !self.strip.is_empty() {
+            out = out.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        apply_case(&out, self.case.unwrap_or(default_case), &self.small_words)
+    }
+}
+
+/// Recases `text` according to `style`; title case keeps `small_words`
+/// lowercase except as the first or last word.
+pub fn apply_case(text: &str, style: CaseStyle, small_words: &HashSet<String>) -> String {
+    match style {
+        CaseStyle::Keep => text.to_string(),
+        CaseStyle::Lower => text.to_lowercase(),
+        CaseStyle::Sentence => {
+            let lower = text.to_lowercase();
+            let mut chars = lower.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        }
+        CaseStyle::Title => {
+            let words: Vec<&str> = text.split_whitespace().collect();
+            let last = words.len().saturating_sub(1);
+            words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    let lower = word.to_lowercase();
+                    if i != 0 && i != last && small_words.contains(&lower) {
+                        return lower;
+                    }
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        None => String::new(),
+                        Some(first) => {
+                            first.to_uppercase().collect::<String>()
+                                + &chars.as_str().to_lowercase()
+                        }
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
+
+static ACTIVE: Lazy<RwLock<Arc<TitleRuleSet>>> =
+    Lazy::new(|| RwLock::new(Arc::new(TitleRuleSet::default())));
+
+/// Makes `rules` the set used by the cleanup helpers in [`crate::utils`]
+/// for the rest of the process (called once after loading the config).
+pub fn set_active(rules: TitleRuleSet) {
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = Arc::new(rules);
+    }
+}
+
+/// The rule set currently in effect (empty unless [`set_active`] was called).
+pub fn active() -> Arc<TitleRuleSet> {
+    ACTIVE.read().map(|a| Arc::clone(&a)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_rules_are_identity_for_keep() {
+        let rules = TitleRules::default().compile().unwrap();
+        assert_eq!(
+            rules.apply("  Odd   SPACING ", CaseStyle::Keep),
+            "  Odd   SPACING "
+        );
+        assert_eq!(
+            rules.apply("dark side OF the moon", CaseStyle::Title),
+            "Dark Side Of The Moon"
+        );
+    }
+
+    #[test]
+    fn test_replace_strip_and_small_words() {
+        let rules = TitleRules {
+            replace: vec![ReplaceRule {
+                pattern: r"\bfeat\.".to_string(),
+                with: "ft.".to_string(),
+            }],
+            strip: vec![r"\(remaster(ed)?( \d{4})?\)".to_string()],
+            case: Some(CaseStyle::Title),
+            small_words: vec!["of".to_string(), "the".to_string(), "ft.".to_string()],
+        }
+        .compile()
+        .unwrap();
+        assert_eq!(
+            rules.apply(
+                "time OF the season (Remastered 2011) Feat. someone",
+                CaseStyle::Keep
+            ),
+            "Time of the Season ft. Someone"
+        );
+    }
+
+    #[test]
+    fn test_sentence_and_lower_case() {
+        let none = HashSet::new();
+        assert_eq!(
+            apply_case("HELLO World", CaseStyle::Sentence, &none),
+            "Hello world"
+        );
+        assert_eq!(
+            apply_case("HELLO World", CaseStyle::Lower, &none),
+            "hello world"
+        );
+        assert_eq!(apply_case("", CaseStyle::Sentence, &none), "");
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_a_config_error() {
+        let rules = TitleRules {
+            strip: vec!["(unclosed".to_string()],
+            ..Default::default()
+        };
+        let err = rules.compile().unwrap_err().to_string();
+        assert!(err.contains("title_rules"), "{}", err);
+    }
+}
@@ -31,6 +31,10 @@ fn skip_ui_output() -> bool {
 pub enum MetadataSource {
     /// Automatically detected from video title
     Detected,
+    /// Reported by the platform (YouTube Music artist/album fields)
+    Platform,
+    /// Guessed from the uploader's channel name (`channel_as_artist`)
+    Channel,
     /// Forced by user via --artist or --album flag
     Forced,
     /// Default value (Unknown Artist)
@@ -42,6 +46,8 @@ impl MetadataSource {
     pub fn label(self) -> &'static str {
         match self {
             MetadataSource::Detected => "detected",
+            MetadataSource::Platform => "platform metadata",
+            MetadataSource::Channel => "channel name",
             MetadataSource::Forced => "user-forced",
             MetadataSource::Default => "default",
         }
@@ -283,6 +289,280 @@ pub fn print_splitting_complete() {
     println!("{} Splitting achieved", "✓".bold());
 }
 
+/// Display the header for single-file output (`output_mode = "single_file"`)
+pub fn print_single_file_section_header(chapters_count: usize) {
+    if skip_ui_output() {
+        return;
+    }
+    println!();
+    print_section_header(&format!(
+        "Writing chaptered file ({} chapters)",
+        chapters_count
+    ));
+}
+
+/// Display the path of the finished chaptered file
+pub fn print_single_file_complete(filename: &str) {
+    if skip_ui_output() {
+        return;
+    }
+    println!("  └─ Saved {}", filename);
+}
+
+/// Display how many SponsorBlock segments were removed or marked
+pub fn print_sponsorblock_segments(action: &str, count: usize, seconds: f64) {
+    if skip_ui_output() {
+        return;
+    }
+    print_section_header("SponsorBlock");
+    println!(
+        "  └─ {} {} segment{} ({})",
+        action,
+        count,
+        if count == 1 { "" } else { "s" },
+        crate::utils::format_duration_short(seconds)
+    );
+}
+
+/// Display the problems found in the chapter list
+pub fn print_chapter_warnings(warnings: &[crate::chapter_validation::ChapterWarning]) {
+    if skip_ui_output() || warnings.is_empty() {
+        return;
+    }
+    print_section_header("Chapter check");
+    for warning in warnings {
+        println!("  ├─ {} {}", "⚠".yellow(), warning);
+    }
+    println!("  └─ Use --review or --edit-chapters to fix the plan before splitting");
+}
+
+/// Display which comment the chapters were read from
+pub fn print_comment_tracklist(tracklist: &crate::comment_tracklist::CommentTracklist) {
+    if skip_ui_output() {
+        return;
+    }
+    print_section_header("Tracklist from comments");
+    println!(
+        "  └─ {} chapters from {}comment{} ({:.0}% of the video)",
+        tracklist.chapters.len(),
+        if tracklist.pinned { "pinned " } else { "" },
+        tracklist
+            .author
+            .as_deref()
+            .map(|a| format!(" by {}", a))
+            .unwrap_or_default(),
+        tracklist.coverage * 100.0
+    );
+}
+
+/// Display the path of the kept full-length audio file (`keep_source_audio`)
+pub fn print_source_audio_kept(filename: &str) {
+    if skip_ui_output() {
+        return;
+    }
+    print_section_header("Full-length audio");
+    println!("  └─ Saved {}", filename);
+}
+
+/// Show the playlist entries as a checklist, all ticked, and let the user
+/// untick the ones to skip. Cancelling (or a terminal the checklist can't
+/// draw on) keeps every entry. Returns the kept entries in list order.
+pub fn prompt_playlist_pick(
+    videos: &[crate::playlist::PlaylistVideo],
+) -> Vec<crate::playlist::PlaylistVideo> {
+    let width = videos.len().to_string().len();
+    let items: Vec<String> = videos
+        .iter()
+        .enumerate()
+        .map(|(i, video)| {
+            let duration = if video.duration > 0.0 {
+                crate::utils::format_duration_short(video.duration)
+            } else {
+                "?".to_string()
+            };
+            format!(
+                "[{:>width$}] {} {}",
+                i + 1,
+                video.title,
+                format!("({})", duration).dimmed(),
+                width = width
+            )
+        })
+        .collect();
+    let picked = dialoguer::MultiSelect::with_theme(&dialoguer::theme::ColorfulTheme::default())
+        .with_prompt("Playlist entries to keep (space toggles, a toggles all, enter confirms, esc keeps all)")
+        .items(&items)
+        .defaults(&vec![true; videos.len()])
+        .max_length(15)
+        .interact_opt();
+    match picked {
+        Ok(Some(indices)) => indices.into_iter().map(|i| videos[i].clone()).collect(),
+        Ok(None) => videos.to_vec(),
+        Err(e) => {
+            log::warn!(
+                "Playlist checklist unavailable ({}); keeping every entry",
+                e
+            );
+            videos.to_vec()
+        }
+    }
+}
+
+/// Show the chapter plan with start, duration and how far refinement (or an
+/// edit) moved each start from the declared one.
+pub fn print_chapter_review(review: &crate::chapter_review::ChapterReview) {
+    let chapters = review.chapters();
+    let width = chapters.len().to_string().len();
+    println!("{} {}", "▶".bold(), "Chapter plan".bold());
+    for (i, chapter) in chapters.iter().enumerate() {
+        let branch = if i + 1 == chapters.len() {
+            "└─"
+        } else {
+            "├─"
+        };
+        let delta = match review.delta(i) {
+            Some(d) if d.abs() >= 0.1 => format!(" Δ{:+.1}s", d).yellow().to_string(),
+            _ => String::new(),
+        };
+        println!(
+            "  {} [{:>width$}] {} {} {}{}",
+            branch,
+            i + 1,
+            crate::chapters::format_timestamp(chapter.start_time).dimmed(),
+            chapter.title,
+            format!(
+                "({})",
+                crate::utils::format_duration_short(chapter.duration())
+            )
+            .dimmed(),
+            delta,
+            width = width
+        );
+    }
+}
+
+/// Let the user edit the chapter plan until they accept it.
+///
+/// # Errors
+///
+/// Returns an error if the user aborts the review
+pub fn prompt_chapter_review(
+    mut review: crate::chapter_review::ChapterReview,
+) -> crate::error::Result<Vec<crate::chapters::Chapter>> {
+    use crate::chapter_review::{ReviewCommand, parse_review_command};
+
+    print_chapter_review(&review);
+    loop {
+        println!("  Edit (r N title, m N, s N time, d N, b N ±secs, u, ?) or Enter to split:");
+        print!("  └─> ");
+        io::stdout().flush().ok();
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).ok();
+        match parse_review_command(&input) {
+            Ok(ReviewCommand::Done) => {
+                println!();
+                return Ok(review.into_chapters());
+            }
+            Ok(ReviewCommand::Abort) => {
+                return Err(crate::error::YtcsError::Other(
+                    "Chapter review aborted".to_string(),
+                ));
+            }
+            Ok(ReviewCommand::Help) => {
+                println!("  r N <title>      rename chapter N");
+                println!("  m N              merge chapter N with N+1");
+                println!(
+                    "  s N <time> [t]   split chapter N at time (e.g. 3:25), new part titled t"
+                );
+                println!("  d N              delete chapter N (not exported)");
+                println!("  b N <±secs|time> move the start of chapter N");
+                println!("  u                undo the last edit");
+                println!("  q                abort without splitting");
+            }
+            Ok(ReviewCommand::Undo) => {
+                if review.undo() {
+                    print_chapter_review(&review);
+                } else {
+                    println!("  {}", "Nothing to undo".yellow());
+                }
+            }
+            Ok(ReviewCommand::Edit(edit)) => match review.apply(&edit) {
+                Ok(()) => print_chapter_review(&review),
+                Err(e) => println!("  {}", e.to_string().yellow()),
+            },
+            Err(e) => println!("  {}", e.to_string().yellow()),
+        }
+    }
+}
+
+/// Display why a video was skipped (its output already exists)
+pub fn print_skipped(reason: &str) {
+    if skip_ui_output() {
+        return;
+    }
+    println!("{} Skipped: {}", "–".bold(), reason);
+}
+
+/// Display the per-video outcome table at the end of a multi-video batch.
+///
+/// In quiet mode only the failed entries are listed, on stderr.
+pub fn print_batch_summary(report: &crate::batch::BatchReport) {
+    use crate::batch::EntryStatus;
+
+    if skip_ui_output() {
+        for entry in &report.entries {
+            if let EntryStatus::Failed { reason, .. } = &entry.status {
+                eprintln!("✗ {} ({})", entry.url, reason);
+            }
+        }
+        return;
+    }
+
+    println!();
+    print_section_header(&format!(
+        "Batch summary: {} done, {} skipped, {} failed",
+        report.done_count(),
+        report.skipped_count(),
+        report.failed_count()
+    ));
+    let width = report.entries.len().to_string().len();
+    for (i, entry) in report.entries.iter().enumerate() {
+        let prefix = if i + 1 == report.entries.len() {
+            "  └─"
+        } else {
+            "  ├─"
+        };
+        let (mark, detail) = match &entry.status {
+            EntryStatus::Done(dir) => ("✓".green(), dir.display().to_string().dimmed()),
+            EntryStatus::Skipped(reason) => ("–".yellow(), reason.as_str().dimmed()),
+            EntryStatus::Failed { reason, message } => (
+                "✗".red(),
+                format!("{}: {}", reason, message.lines().next().unwrap_or("")).red(),
+            ),
+        };
+        println!(
+            "{} {:>width$} {} {}  {}",
+            prefix.dimmed(),
+            i + 1,
+            mark,
+            entry.url,
+            detail,
+            width = width
+        );
+    }
+}
+
+/// Display where the failed URLs of a batch were written
+pub fn print_failures_list_written(path: &std::path::Path) {
+    if skip_ui_output() {
+        eprintln!("Failed URLs written to {}", path.display());
+        return;
+    }
+    println!();
+    println!("Failed URLs written to {}", path.display());
+}
+
 /// Display final success with output directory
 pub fn print_final_result(output_dir: &std::path::Path) {
     if skip_ui_output() {
@@ -1,3 +1,4 @@
+use crate::title_rules::{self, CaseStyle};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -17,34 +18,33 @@ static RE_BRACKETS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[.*?\]|\(.*?\)").un
 
 static RE_SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
 
+/// Channel-name decorations that aren't part of the artist's name.
+static RE_CHANNEL_SUFFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)(?:\s*-\s*topic|vevo|\s*[\[(]?\bofficial(?:\s+(?:channel|music|artist|youtube))?\b[\])]?)\s*$",
+    )
+    .unwrap()
+});
+
+/// A year alone in brackets: `[1973]`, `(1973)`.
+static RE_TITLE_YEAR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[\[(]\s*((?:19|20)\d{2})\s*[\])]").unwrap());
+
+/// `Released: 1973`, `Release date: March 1, 1973`, `Year: 1973` at the start of a line.
+static RE_DESCRIPTION_RELEASE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?im)^\s*(?:originally\s+)?(?:released?(?:\s+(?:on|in|date))?|year)\b[^\n]{0,30}?\b((?:19|20)\d{2})\b",
+    )
+    .unwrap()
+});
+
+/// `(c) 1973`, `© 1973`, `℗ 1973`, `(P) 1973`.
+static RE_DESCRIPTION_COPYRIGHT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:\(c\)|©|℗|\(p\))\s*((?:19|20)\d{2})\b").unwrap());
+
 static RE_TRACK_PREFIX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^\s*(?:Track\s+)?\d+\s*[-.:)]?\s+").unwrap());
 
-/// Converts a string to title case (first letter of each word capitalized).
-///
-/// # Arguments
-///
-/// * `s` - The input string
-///
-/// # Returns
-///
-/// A title-cased string
-fn to_title_case(s: &str) -> String {
-    s.split_whitespace()
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                None => String::new(),
-                Some(first) => {
-                    first.to_uppercase().collect::<String>()
-                        + &chars.collect::<String>().to_lowercase()
-                }
-            }
-        })
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
 /// Cleans and formats a folder name according to defined rules.
 ///
 /// This function applies several transformations to normalize folder names:
@@ -53,7 +53,8 @@ fn to_title_case(s: &str) -> String {
 /// - Removes all brackets `[]` and parentheses `()` with their content
 /// - Replaces underscores `_`, pipes `|` and slashes `/` with dashes `-`
 /// - Normalizes multiple spaces into a single space
-/// - Capitalizes each word (first letter uppercase, rest lowercase)
+/// - Applies the configured [`crate::title_rules`], then capitalizes each word
+///   (first letter uppercase, rest lowercase) unless the rules set another case style
 /// - Removes spaces and dashes at the beginning/end of the string
 ///
 /// # Arguments
@@ -88,21 +89,8 @@ pub fn clean_folder_name(name: &str) -> String {
     // Clean multiple spaces
     let normalized = RE_SPACES.replace_all(&with_dashes, " ");
 
-    // Capitalize each word
-    let capitalized = normalized
-        .split_whitespace()
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                None => String::new(),
-                Some(first) => {
-                    first.to_uppercase().collect::<String>()
-                        + chars.as_str().to_lowercase().as_str()
-                }
-            }
-        })
-        .collect::<Vec<String>>()
-        .join(" ");
+    // User rules, then capitalize each word (or the configured case style)
+    let capitalized = title_rules::active().apply(&normalized, CaseStyle::Title);
 
     // Clean dashes and spaces at beginning/end
     capitalized.trim().trim_matches('-').trim().to_string()
@@ -269,6 +257,99 @@ pub fn parse_artist_album_with_source(
     }
 }
 
+/// Artist and album for a video: the platform's music metadata (YouTube Music
+/// `artist` / `album`) when present, otherwise parsed from the title by
+/// [`parse_artist_album_with_source`]. Each field falls back separately.
+pub fn artist_album_from_info(
+    info: &crate::downloader::VideoInfo,
+) -> (
+    (String, String),
+    crate::ui::MetadataSource,
+    crate::ui::MetadataSource,
+) {
+    use crate::ui::MetadataSource;
+
+    let ((parsed_artist, parsed_album), parsed_artist_src, parsed_album_src) =
+        parse_artist_album_with_source(&info.title);
+    let (artist, artist_src) = match info.music.artist.as_deref().map(clean_folder_name) {
+        Some(a) if !a.is_empty() => (a, MetadataSource::Platform),
+        _ => (parsed_artist, parsed_artist_src),
+    };
+    let (album, album_src) = match info.music.album.as_deref().map(clean_folder_name) {
+        Some(al) if !al.is_empty() => (al, MetadataSource::Platform),
+        _ => (parsed_album, parsed_album_src),
+    };
+    ((artist, album), artist_src, album_src)
+}
+
+/// Turns a channel name into an artist candidate by dropping ` - Topic`,
+/// `VEVO` and `Official …` decorations (`PinkFloydVEVO` → `PinkFloyd`,
+/// `Marigold Official` → `Marigold`).
+///
+/// Returns `None` if nothing is left.
+///
+/// # Examples
+///
+/// ```
+/// use youtube_chapter_splitter::utils::artist_from_channel;
+///
+/// assert_eq!(artist_from_channel("Pink Floyd - Topic").as_deref(), Some("Pink Floyd"));
+/// assert_eq!(artist_from_channel("Marigold (Official)").as_deref(), Some("Marigold"));
+/// assert_eq!(artist_from_channel("VEVO"), None);
+/// ```
+pub fn artist_from_channel(channel: &str) -> Option<String> {
+    let mut name = channel.trim().to_string();
+    loop {
+        let stripped = RE_CHANNEL_SUFFIX.replace(&name, "").trim().to_string();
+        if stripped == name {
+            break;
+        }
+        name = stripped;
+    }
+    let name = name.trim_end_matches(['-', '|', ':']).trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Finds the release year hinted at by a title (`Pink Floyd - Dark Side [1973]`)
+/// or description (`Released: 1973`, `(c) 1973`), in that order of preference.
+///
+/// Years before 1900 or after next year are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use youtube_chapter_splitter::utils::extract_release_year;
+///
+/// assert_eq!(extract_release_year("Pink Floyd - Dark Side [1973]", None), Some(1973));
+/// assert_eq!(extract_release_year("Live Set", Some("Recorded live.\nReleased: 2019")), Some(2019));
+/// assert_eq!(extract_release_year("Pink Floyd - Dark Side", None), None);
+/// ```
+pub fn extract_release_year(title: &str, description: Option<&str>) -> Option<u32> {
+    let max_year = u32::try_from(time::OffsetDateTime::now_utc().year() + 1).unwrap_or(u32::MAX);
+    let plausible = |caps: regex::Captures| -> Option<u32> {
+        caps.get(1)?
+            .as_str()
+            .parse::<u32>()
+            .ok()
+            .filter(|y| (1900..=max_year).contains(y))
+    };
+
+    RE_TITLE_YEAR
+        .captures_iter(title)
+        .find_map(plausible)
+        .or_else(|| {
+            let description = description?;
+            RE_DESCRIPTION_RELEASE
+                .captures_iter(description)
+                .find_map(plausible)
+                .or_else(|| {
+                    RE_DESCRIPTION_COPYRIGHT
+                        .captures_iter(description)
+                        .find_map(plausible)
+                })
+        })
+}
+
 /// Replaces characters that are invalid in path components on Windows and problematic on Unix.
 ///
 /// Maps `/ \ : * ? " < > |` to `_`. Use when building directory or file names from user/metadata
@@ -291,7 +372,8 @@ pub fn sanitize_filesystem_chars(s: &str) -> String {
 ///
 /// - Removes prefixes like `"1 - "`, `"01. "`, `"Track 5: "`
 /// - Replaces forbidden characters (`/`, `\`, `:`, `*`, `?`, `"`, `<`, `>`, `|`) with `_`
-/// - Applies title case (first letter of each word capitalized)
+/// - Applies the configured [`crate::title_rules`] and title case (first letter
+///   of each word capitalized) unless the rules set another case style
 ///
 /// # Arguments
 ///
@@ -307,14 +389,22 @@ pub fn sanitize_filesystem_chars(s: &str) -> String {
 /// use youtube_chapter_splitter::utils::sanitize_title;
 ///
 /// assert_eq!(sanitize_title("1 - Song Name"), "Song Name");
-/// // Note: to_title_case treats underscores as part of words, so only first char is capitalized
+/// // Note: title case sees `Test/Song` as one word, so only the first char is capitalized
 /// assert_eq!(sanitize_title("Track 5: Test/Song"), "Test_song");
 /// ```
 pub fn sanitize_title(title: &str) -> String {
     // Remove track numbers at the beginning
     let title = RE_TRACK_PREFIX.replace(title, "");
-    let sanitized = sanitize_filesystem_chars(title.as_ref());
-    to_title_case(&sanitized)
+    let cased = title_rules::active().apply(&title, CaseStyle::Title);
+    sanitize_filesystem_chars(&cased)
+}
+
+/// Applies the configured [`crate::title_rules`] to a track title tag.
+///
+/// Without a configured case style the title keeps its published casing, so
+/// with no rules at all this returns the title unchanged.
+pub fn clean_track_title(title: &str) -> String {
+    title_rules::active().apply(title, CaseStyle::Keep)
 }
 
 /// Converts yt-dlp `upload_date` (YYYYMMDD) to `YYYY-MM-DD` for tag `date` fields.
@@ -5,133 +5,199 @@
 
 use crate::cookie_helper;
 
-/// Parse a yt-dlp error message and return a user-friendly version.
-///
-/// # Arguments
-///
-/// * `raw_error` - The raw error message from yt-dlp stderr
-/// * `cookies_from_browser` - Optional browser name configured for cookies
-///
-/// # Returns
+/// Broad reason a yt-dlp call failed; used to group failures in batch summaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// No JavaScript runtime to solve YouTube's `n` challenge
+    JsRuntime,
+    /// Bot detection / HTTP 429
+    RateLimited,
+    /// Members-only or private content
+    AuthRequired,
+    /// Age-restricted content
+    AgeRestricted,
+    /// Not available in this country
+    GeoRestricted,
+    /// Deleted or removed video
+    Unavailable,
+    /// Connection problems / HTTP errors
+    Network,
+    /// URL yt-dlp doesn't understand
+    InvalidUrl,
+    /// Anything else
+    Other,
+}
+
+impl ErrorCategory {
+    /// Short label for summary tables
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorCategory::JsRuntime => "no JS runtime",
+            ErrorCategory::RateLimited => "rate limited",
+            ErrorCategory::AuthRequired => "sign-in required",
+            ErrorCategory::AgeRestricted => "age-restricted",
+            ErrorCategory::GeoRestricted => "geo-restricted",
+            ErrorCategory::Unavailable => "unavailable",
+            ErrorCategory::Network => "network",
+            ErrorCategory::InvalidUrl => "invalid URL",
+            ErrorCategory::Other => "other",
+        }
+    }
+}
+
+/// Classify a yt-dlp error.
 ///
-/// A tuple of (error_message, optional_suggestion)
-pub fn parse_ytdlp_error(
-    raw_error: &str,
-    cookies_from_browser: Option<&str>,
-) -> (String, Option<String>) {
-    let error_lower = raw_error.to_lowercase();
+/// Accepts both raw yt-dlp stderr and the friendly messages produced by
+/// [`parse_ytdlp_error`], so an error that has already been turned into a
+/// `YtcsError::DownloadError` can still be grouped.
+pub fn categorize_ytdlp_error(error: &str) -> ErrorCategory {
+    let error_lower = error.to_lowercase();
 
-    // Check for missing JS runtime (YouTube's `n` challenge)
-    // yt-dlp needs deno (or another JS runtime) to resolve audio formats.
+    // Missing JS runtime (YouTube's `n` challenge): yt-dlp needs deno (or
+    // another JS runtime) to resolve audio formats.
     if error_lower.contains("n challenge")
         || error_lower.contains("javascript runtime")
         || (error_lower.contains("requested format is not available")
             && error_lower.contains("only images are available"))
     {
-        return (
-            "yt-dlp cannot resolve audio formats: no JavaScript runtime found.".to_string(),
-            Some(
-                "YouTube now requires a JS runtime to solve its `n` challenge.\n  \
-                 Install deno (recommended):\n    \
-                 curl -fsSL https://deno.land/install.sh | sh\n  \
-                 Then add to PATH:\n    \
-                 echo 'export PATH=\"$HOME/.deno/bin:$PATH\"' >> ~/.bashrc && source ~/.bashrc"
-                    .to_string(),
-            ),
-        );
+        return ErrorCategory::JsRuntime;
     }
 
-    // Check for YouTube bot-detection / rate limiting
+    // YouTube bot-detection / rate limiting
     // ("Sign in to confirm you're not a bot" or HTTP 429 Too Many Requests)
     if error_lower.contains("not a bot")
         || error_lower.contains("confirm you're not")
         || error_lower.contains("http error 429")
         || error_lower.contains("too many requests")
+        || error_lower.contains("rate-limiting")
     {
-        let message =
-            "YouTube is rate-limiting this IP and asks for a signed-in session to continue."
-                .to_string();
-        let suggestion = if cookie_helper::cookies_available(cookies_from_browser) {
-            Some("Your configured cookies were rejected or are stale. Try:\n  1. Log in to YouTube in your browser, then retry\n  2. Re-export cookies to ~/.config/ytcs/cookies.txt\n  3. Wait a few minutes — the rate limit clears on its own".to_string())
-        } else {
-            Some("Configure cookies so yt-dlp can authenticate:\n  • ytcs config → 'Cookies from browser' (chrome, firefox, brave, …)\n  • For LibreWolf / custom profile: use 'firefox:/path/to/profile'\n  • Or export a cookies.txt to ~/.config/ytcs/cookies.txt".to_string())
-        };
-        return (message, suggestion);
-    }
-
-    // Check for authentication/membership errors
+        return ErrorCategory::RateLimited;
+    }
+
     if error_lower.contains("members-only")
         || error_lower.contains("this video is only available")
         || error_lower.contains("join this channel")
         || error_lower.contains("private video")
         || error_lower.contains("sign in to confirm")
+        || error_lower.contains("requires authentication")
     {
-        let message =
-            "This video requires authentication (member-only or private content)".to_string();
-        let suggestion = if cookie_helper::cookies_available(cookies_from_browser) {
-            Some("Your cookies may have expired. Try:\n  1. Export fresh cookies from your browser\n  2. Update ~/.config/ytcs/cookies.txt\n  3. Or run: ytcs config (set Cookies from browser)".to_string())
-        } else {
-            Some("You need to authenticate. Choose one option:\n  1. Export cookies: see COOKIES_SETUP.md\n  2. Or run: ytcs config (set Cookies from browser)".to_string())
-        };
-        return (message, suggestion);
+        return ErrorCategory::AuthRequired;
     }
 
-    // Check for age-restricted content
     if error_lower.contains("age-restricted") || error_lower.contains("age restricted") {
-        let message = "This video is age-restricted".to_string();
-        let suggestion = if cookie_helper::cookies_available(cookies_from_browser) {
-            Some("Your cookies may not have the required age verification. Try logging in to YouTube in your browser and exporting fresh cookies.".to_string())
-        } else {
-            Some("You need to authenticate with an age-verified account:\n  1. Log in to YouTube in your browser\n  2. Export cookies (see COOKIES_SETUP.md)\n  3. Or run: ytcs config (set Cookies from browser)".to_string())
-        };
-        return (message, suggestion);
+        return ErrorCategory::AgeRestricted;
     }
 
-    // Check for geo-blocking
     if error_lower.contains("not available in your country")
         || error_lower.contains("geo-restricted")
         || error_lower.contains("blocked in your country")
     {
-        return (
-            "This video is not available in your country (geo-restricted)".to_string(),
-            Some("You may need to use a VPN or proxy to access this content.".to_string()),
-        );
+        return ErrorCategory::GeoRestricted;
     }
 
-    // Check for video unavailable/deleted
     if error_lower.contains("video unavailable")
         || error_lower.contains("has been removed")
         || error_lower.contains("this video is no longer available")
     {
-        return (
-            "This video is no longer available (deleted or made private)".to_string(),
-            None,
-        );
+        return ErrorCategory::Unavailable;
     }
 
-    // Check for network errors
     if error_lower.contains("unable to download")
         || error_lower.contains("http error")
         || error_lower.contains("connection")
         || error_lower.contains("timeout")
+        || error_lower.contains("network error")
     {
-        return (
-            "Network error while downloading".to_string(),
-            Some("Check your internet connection and try again.".to_string()),
-        );
+        return ErrorCategory::Network;
     }
 
-    // Check for invalid URL
-    if error_lower.contains("invalid url") || error_lower.contains("unsupported url") {
-        return (
-            "Invalid or unsupported YouTube URL".to_string(),
-            Some("Make sure you're using a valid YouTube video URL.".to_string()),
-        );
+    if error_lower.contains("invalid url")
+        || error_lower.contains("unsupported url")
+        || error_lower.contains("invalid or unsupported")
+    {
+        return ErrorCategory::InvalidUrl;
     }
 
-    // Default: return cleaned error message
-    let cleaned_error = clean_error_message(raw_error);
-    (cleaned_error, None)
+    ErrorCategory::Other
+}
+
+/// Parse a yt-dlp error message and return a user-friendly version.
+///
+/// # Arguments
+///
+/// * `raw_error` - The raw error message from yt-dlp stderr
+/// * `cookies_from_browser` - Optional browser name configured for cookies
+///
+/// # Returns
+///
+/// A tuple of (error_message, optional_suggestion)
+pub fn parse_ytdlp_error(
+    raw_error: &str,
+    cookies_from_browser: Option<&str>,
+) -> (String, Option<String>) {
+    match categorize_ytdlp_error(raw_error) {
+        ErrorCategory::JsRuntime => (
+            "yt-dlp cannot resolve audio formats: no JavaScript runtime found.".to_string(),
+            Some(
+                "YouTube now requires a JS runtime to solve its `n` challenge.\n  \
+                 Install deno (recommended):\n    \
+                 curl -fsSL https://deno.land/install.sh | sh\n  \
+                 Then add to PATH:\n    \
+                 echo 'export PATH=\"$HOME/.deno/bin:$PATH\"' >> ~/.bashrc && source ~/.bashrc"
+                    .to_string(),
+            ),
+        ),
+        ErrorCategory::RateLimited => {
+            let message =
+                "YouTube is rate-limiting this IP and asks for a signed-in session to continue."
+                    .to_string();
+            let suggestion = if cookie_helper::cookies_available(cookies_from_browser) {
+                Some("Your configured cookies were rejected or are stale. Try:\n  1. Log in to YouTube in your browser, then retry\n  2. Re-export cookies to ~/.config/ytcs/cookies.txt\n  3. Wait a few minutes — the rate limit clears on its own".to_string())
+            } else {
+                Some("Configure cookies so yt-dlp can authenticate:\n  • ytcs config → 'Cookies from browser' (chrome, firefox, brave, …)\n  • For LibreWolf / custom profile: use 'firefox:/path/to/profile'\n  • Or export a cookies.txt to ~/.config/ytcs/cookies.txt".to_string())
+            };
+            (message, suggestion)
+        }
+        ErrorCategory::AuthRequired => {
+            let message =
+                "This video requires authentication (member-only or private content)".to_string();
+            let suggestion = if cookie_helper::cookies_available(cookies_from_browser) {
+                Some("Your cookies may have expired. Try:\n  1. Export fresh cookies from your browser\n  2. Update ~/.config/ytcs/cookies.txt\n  3. Or run: ytcs config (set Cookies from browser)".to_string())
+            } else {
+                Some("You need to authenticate. Choose one option:\n  1. Export cookies: see COOKIES_SETUP.md\n  2. Or run: ytcs config (set Cookies from browser)".to_string())
+            };
+            (message, suggestion)
+        }
+        ErrorCategory::AgeRestricted => {
+            let message = "This video is age-restricted".to_string();
+            let suggestion = if cookie_helper::cookies_available(cookies_from_browser) {
+                Some("Your cookies may not have the required age verification. Try logging in to YouTube in your browser and exporting fresh cookies.".to_string())
+            } else {
+                Some("You need to authenticate with an age-verified account:\n  1. Log in to YouTube in your browser\n  2. Export cookies (see COOKIES_SETUP.md)\n  3. Or run: ytcs config (set Cookies from browser)".to_string())
+            };
+            (message, suggestion)
+        }
+        ErrorCategory::GeoRestricted => (
+            "This video is not available in your country (geo-restricted)".to_string(),
+            Some("You may need to use a VPN or proxy to access this content.".to_string()),
+        ),
+        ErrorCategory::Unavailable => (
+            "This video is no longer available (deleted or made private)".to_string(),
+            None,
+        ),
+        ErrorCategory::Network => (
+            "Network error while downloading".to_string(),
+            Some("Check your internet connection and try again.".to_string()),
+        ),
+        ErrorCategory::InvalidUrl => (
+            "Invalid or unsupported URL".to_string(),
+            Some(
+                "Make sure the URL points at a video on a site yt-dlp supports (run `yt-dlp --list-extractors` to see them)."
+                    .to_string(),
+            ),
+        ),
+        ErrorCategory::Other => (clean_error_message(raw_error), None),
+    }
 }
 
 /// Clean up a raw yt-dlp error message by removing technical noise.
@@ -235,6 +301,28 @@ mod tests {
         assert!(suggestion.is_some());
     }
 
+    #[test]
+    fn test_categorize_accepts_friendly_messages() {
+        for raw in [
+            "ERROR: [youtube] abc: Sign in to confirm you're not a bot.",
+            "ERROR: Video unavailable",
+            "ERROR: This video is age-restricted",
+            "ERROR: HTTP Error 500: Internal Server Error",
+        ] {
+            let (friendly, _) = parse_ytdlp_error(raw, None);
+            assert_eq!(
+                categorize_ytdlp_error(&friendly),
+                categorize_ytdlp_error(raw),
+                "{}",
+                raw
+            );
+        }
+        assert_eq!(
+            categorize_ytdlp_error("something odd happened"),
+            ErrorCategory::Other
+        );
+    }
+
     #[test]
     fn test_clean_error_message() {
         let error = "ERROR: [youtube] Something went wrong with the download";
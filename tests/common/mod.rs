@@ -0,0 +1,13 @@
+//! Helpers shared by the integration tests.
+
+use std::process::Command;
+
+/// Whether a working `ffmpeg` is on the PATH; tests that need it return early
+/// without it.
+pub fn ffmpeg_available() -> bool {
+    Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
@@ -0,0 +1,102 @@
+//! Tests for `audio::write_album_track`: one downloaded video tagged as a
+//! numbered track of a playlist album.
+//!
+//! The tagging test needs a local `ffmpeg`/`ffprobe`; it is skipped when
+//! ffmpeg is not installed.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use youtube_chapter_splitter::audio;
+
+mod common;
+use common::ffmpeg_available;
+
+fn test_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("ytcs_album_track_{}", name));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    root
+}
+
+#[test]
+fn test_write_album_track_refuses_existing_target_without_overwrite() {
+    let root = test_root("existing");
+    let target = root.join("03 - Song.mp3");
+    fs::write(&target, b"previous run").unwrap();
+
+    let result = audio::write_album_track(
+        &root.join("temp_audio_003.mp3"),
+        &target,
+        3,
+        10,
+        "Song",
+        "Artist",
+        "Album",
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(fs::read(&target).unwrap(), b"previous run");
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_write_album_track_sets_track_number() {
+    if !ffmpeg_available() {
+        eprintln!("Skipping: ffmpeg not available");
+        return;
+    }
+    let root = test_root("tags");
+    let source = root.join("temp_audio_002.mp3");
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            "anullsrc=r=44100:cl=mono",
+            "-t",
+            "1",
+            "-c:a",
+            "libmp3lame",
+            source.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success());
+
+    let target = root.join("02 - Second Song.mp3");
+    audio::write_album_track(
+        &source,
+        &target,
+        2,
+        7,
+        "Second Song",
+        "Artist",
+        "Album",
+        None,
+        None,
+        None,
+        None,
+        false,
+    )
+    .unwrap();
+
+    let probe = Command::new("ffprobe")
+        .args(["-v", "error", "-show_format", "-of", "json"])
+        .arg(&target)
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&probe.stdout).unwrap();
+    let tags = &json["format"]["tags"];
+    assert_eq!(tags["title"], "Second Song");
+    assert_eq!(tags["track"], "2/7");
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -11,13 +11,8 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use youtube_chapter_splitter::{AudioFormat, Chapter, audio};
 
-fn ffmpeg_available() -> bool {
-    Command::new("ffmpeg")
-        .arg("-version")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
-}
+mod common;
+use common::ffmpeg_available;
 
 /// Creates a `duration_secs`-long silent mono audio fixture (and a fresh,
 /// empty output directory next to it) under a unique temp directory.
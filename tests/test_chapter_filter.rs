@@ -0,0 +1,65 @@
+use youtube_chapter_splitter::ExcludedChapters;
+use youtube_chapter_splitter::chapter_filter::ChapterFilter;
+use youtube_chapter_splitter::chapters::Chapter;
+
+fn chapters() -> Vec<Chapter> {
+    vec![
+        Chapter::new("Intro".to_string(), 0.0, 30.0),
+        Chapter::new("First Song".to_string(), 30.0, 200.0),
+        Chapter::new("Sponsor: VPN".to_string(), 200.0, 260.0),
+        Chapter::new("Second Song".to_string(), 260.0, 500.0),
+        Chapter::new("Outro / Credits".to_string(), 500.0, 540.0),
+    ]
+}
+
+fn spans(chapters: &[Chapter]) -> Vec<(&str, f64, f64)> {
+    chapters
+        .iter()
+        .map(|c| (c.title.as_str(), c.start_time, c.end_time))
+        .collect()
+}
+
+#[test]
+fn test_exclude_drops_audio() {
+    let filter =
+        ChapterFilter::new(None, Some("^(intro|outro|sponsor)"), ExcludedChapters::Drop).unwrap();
+    let kept = filter.apply(&chapters()).unwrap();
+    assert_eq!(
+        spans(&kept),
+        vec![("First Song", 30.0, 200.0), ("Second Song", 260.0, 500.0)]
+    );
+}
+
+#[test]
+fn test_exclude_merges_into_neighbours() {
+    let filter = ChapterFilter::new(
+        None,
+        Some("^(intro|outro|sponsor)"),
+        ExcludedChapters::Merge,
+    )
+    .unwrap();
+    let kept = filter.apply(&chapters()).unwrap();
+    // The intro joins the first track, the others extend the track before them.
+    assert_eq!(
+        spans(&kept),
+        vec![("First Song", 0.0, 260.0), ("Second Song", 260.0, 540.0)]
+    );
+}
+
+#[test]
+fn test_include_pattern_is_case_insensitive() {
+    let filter = ChapterFilter::new(Some("SONG"), None, ExcludedChapters::Drop).unwrap();
+    assert_eq!(filter.apply(&chapters()).unwrap().len(), 2);
+    assert!(filter.keeps("first song"));
+    assert!(!filter.keeps("Intro"));
+}
+
+#[test]
+fn test_filter_errors() {
+    assert!(ChapterFilter::new(Some("(unclosed"), None, ExcludedChapters::Drop).is_err());
+    let filter = ChapterFilter::new(None, Some("."), ExcludedChapters::Merge).unwrap();
+    assert!(filter.apply(&chapters()).is_err());
+    let all = ChapterFilter::default();
+    assert!(all.is_all());
+    assert_eq!(all.apply(&chapters()).unwrap().len(), 5);
+}
@@ -0,0 +1,195 @@
+use youtube_chapter_splitter::chapter_review::*;
+use youtube_chapter_splitter::chapters::Chapter;
+
+fn plan() -> Vec<Chapter> {
+    vec![
+        Chapter::new("One".to_string(), 0.0, 60.0),
+        Chapter::new("Two".to_string(), 60.0, 120.0),
+        Chapter::new("Three".to_string(), 120.0, 200.0),
+    ]
+}
+
+fn edit(review: &mut ChapterReview, command: &str) {
+    match parse_review_command(command).unwrap() {
+        ReviewCommand::Edit(edit) => review.apply(&edit).unwrap(),
+        other => panic!("{:?} is not an edit", other),
+    }
+}
+
+fn summary(review: &ChapterReview) -> Vec<(String, f64, f64)> {
+    review
+        .chapters()
+        .iter()
+        .map(|c| (c.title.clone(), c.start_time, c.end_time))
+        .collect()
+}
+
+#[test]
+fn test_parse_review_commands() {
+    assert_eq!(parse_review_command("").unwrap(), ReviewCommand::Done);
+    assert_eq!(parse_review_command(" q ").unwrap(), ReviewCommand::Abort);
+    assert_eq!(parse_review_command("u").unwrap(), ReviewCommand::Undo);
+    assert_eq!(
+        parse_review_command("r 2 New  Title").unwrap(),
+        ReviewCommand::Edit(ChapterEdit::Rename {
+            index: 1,
+            title: "New  Title".to_string()
+        })
+    );
+    assert_eq!(
+        parse_review_command("s 1 0:30 Reprise").unwrap(),
+        ReviewCommand::Edit(ChapterEdit::Split {
+            index: 0,
+            at: 30.0,
+            title: Some("Reprise".to_string())
+        })
+    );
+    assert_eq!(
+        parse_review_command("b 3 -2.5").unwrap(),
+        ReviewCommand::Edit(ChapterEdit::Shift {
+            index: 2,
+            shift: Shift::By(-2.5)
+        })
+    );
+    assert_eq!(
+        parse_review_command("b 3 2:05").unwrap(),
+        ReviewCommand::Edit(ChapterEdit::Shift {
+            index: 2,
+            shift: Shift::To(125.0)
+        })
+    );
+    for bad in ["r 2", "m", "d 0", "s 1 soon", "b 2 +x", "x 1"] {
+        assert!(parse_review_command(bad).is_err(), "{:?} should fail", bad);
+    }
+}
+
+#[test]
+fn test_merge_split_delete_rename() {
+    let mut review = ChapterReview::new(&plan(), plan());
+    edit(&mut review, "m 1");
+    assert_eq!(
+        summary(&review),
+        vec![
+            ("One".to_string(), 0.0, 120.0),
+            ("Three".to_string(), 120.0, 200.0)
+        ]
+    );
+    edit(&mut review, "s 2 2:40");
+    edit(&mut review, "r 3 Outro");
+    edit(&mut review, "d 1");
+    assert_eq!(
+        summary(&review),
+        vec![
+            ("Three".to_string(), 120.0, 160.0),
+            ("Outro".to_string(), 160.0, 200.0)
+        ]
+    );
+}
+
+#[test]
+fn test_shift_moves_shared_cut_and_reports_delta() {
+    let declared = plan();
+    let mut refined = plan();
+    refined[1].start_time = 61.5;
+    refined[0].end_time = 61.5;
+    let mut review = ChapterReview::new(&declared, refined);
+    assert_eq!(review.delta(1), Some(1.5));
+
+    edit(&mut review, "b 2 +1");
+    assert_eq!(review.chapters()[0].end_time, 62.5);
+    assert_eq!(review.chapters()[1].start_time, 62.5);
+    assert_eq!(review.delta(1), Some(2.5));
+
+    edit(&mut review, "s 2 1:30");
+    assert_eq!(review.delta(2), None, "split parts have no declared start");
+}
+
+#[test]
+fn test_invalid_edits_leave_plan_unchanged() {
+    let mut review = ChapterReview::new(&plan(), plan());
+    let before = summary(&review);
+    for command in ["m 3", "d 4", "s 1 1:30", "b 2 +60", "b 1 -5"] {
+        let ReviewCommand::Edit(e) = parse_review_command(command).unwrap() else {
+            panic!("{:?} is not an edit", command);
+        };
+        assert!(review.apply(&e).is_err(), "{:?} should fail", command);
+    }
+    assert_eq!(summary(&review), before);
+    assert!(!review.undo());
+}
+
+#[test]
+fn test_undo_restores_previous_plan() {
+    let mut review = ChapterReview::new(&plan(), plan());
+    edit(&mut review, "d 2");
+    edit(&mut review, "r 1 First");
+    assert!(review.undo());
+    assert!(review.undo());
+    assert_eq!(
+        summary(&review),
+        summary(&ChapterReview::new(&plan(), plan()))
+    );
+    assert_eq!(review.into_chapters().len(), 3);
+}
+
+#[test]
+fn test_chapter_plan_round_trip_keeps_refined_starts() {
+    let mut refined = plan();
+    refined[1].start_time = 61.5;
+    refined[0].end_time = 61.5;
+    let text = format_chapter_plan(&refined, "Artist - Album", 200.0);
+    assert!(text.starts_with("# Artist - Album\n# Duration: 00:03:20"));
+    assert!(text.contains("\n00:01:01 Two\n"));
+
+    let parsed = parse_chapter_plan(&text, 200.0, &refined).unwrap();
+    assert_eq!(parsed.len(), 3);
+    assert_eq!(parsed[0].end_time, 61.5);
+    assert_eq!(parsed[1].start_time, 61.5);
+    assert_eq!(parsed[2].end_time, 200.0);
+}
+
+#[test]
+fn test_chapter_plan_keeps_gaps_left_by_the_chapter_filter() {
+    // "Two" (60-120) and the trailing "Outro" (180-200) were dropped.
+    let filtered = vec![
+        Chapter::new("One".to_string(), 0.0, 60.0),
+        Chapter::new("Three".to_string(), 120.0, 180.0),
+    ];
+    let text = format_chapter_plan(&filtered, "Artist - Album", 200.0);
+    let edited = text.replace("00:00:00 One", "00:00:00 Opening");
+
+    let parsed = parse_chapter_plan(&edited, 200.0, &filtered).unwrap();
+    assert_eq!(parsed[0].title, "Opening");
+    assert_eq!((parsed[0].start_time, parsed[0].end_time), (0.0, 60.0));
+    assert_eq!((parsed[1].start_time, parsed[1].end_time), (120.0, 180.0));
+
+    // A new line after a gap closes it up to the next start.
+    let edited = format!("{}00:02:40 Coda\n", text);
+    let parsed = parse_chapter_plan(&edited, 200.0, &filtered).unwrap();
+    assert_eq!(parsed[1].end_time, 160.0);
+    assert_eq!((parsed[2].start_time, parsed[2].end_time), (160.0, 200.0));
+}
+
+#[test]
+fn test_chapter_plan_edits_are_validated() {
+    let edited = "# header\n00:00:00 One\n00:00:50 Two\n# note\n00:02:30 Three\n";
+    let parsed = parse_chapter_plan(edited, 200.0, &plan()).unwrap();
+    assert_eq!(
+        parsed.iter().map(|c| c.start_time).collect::<Vec<_>>(),
+        vec![0.0, 50.0, 150.0]
+    );
+
+    let cases = [
+        ("00:00:00 One\n00:02:00 Two\n00:01:00 Three\n", "line 3"),
+        ("00:00:00 One\n00:04:00 Two\n", "past the end"),
+        ("00:00:00 One\nTwo\n", "line 2"),
+        ("00:00:00 One\n00:01:00\n", "missing title"),
+        ("00:00:00 One\n", "at least 2"),
+    ];
+    for (text, expected) in cases {
+        let err = parse_chapter_plan(text, 200.0, &[])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(expected), "{:?}: {}", text, err);
+    }
+}
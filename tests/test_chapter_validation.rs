@@ -0,0 +1,202 @@
+use youtube_chapter_splitter::chapter_validation::*;
+use youtube_chapter_splitter::chapters::Chapter;
+
+/// Chapters from `(title, start, end)` without `Chapter::new`'s checks.
+fn chapters(spans: &[(&str, f64, f64)]) -> Vec<Chapter> {
+    spans
+        .iter()
+        .map(|(title, start, end)| Chapter {
+            title: title.to_string(),
+            start_time: *start,
+            end_time: *end,
+        })
+        .collect()
+}
+
+#[test]
+fn test_validate_accepts_clean_chapters() {
+    let list = chapters(&[("One", 0.0, 200.0), ("Two", 200.0, 420.0)]);
+    assert!(validate_chapters(&list, Some(421.0)).is_empty());
+    assert!(validate_chapters(&list, None).is_empty());
+    assert!(validate_chapters(&[], Some(100.0)).is_empty());
+}
+
+#[test]
+fn test_validate_flags_order_and_duplicates() {
+    let list = chapters(&[
+        ("One", 0.0, 200.0),
+        ("Two", 200.0, 400.0),
+        ("Two again", 200.2, 400.0),
+        ("Chorus", 90.0, 400.0),
+        ("Broken", 300.0, 250.0),
+    ]);
+    let warnings = validate_chapters(&list, None);
+    assert_eq!(
+        warnings,
+        vec![
+            ChapterWarning::DuplicateStart {
+                index: 2,
+                title: "Two again".to_string(),
+                start: 200.2,
+            },
+            ChapterWarning::OutOfOrder {
+                index: 3,
+                title: "Chorus".to_string(),
+                start: 90.0,
+                previous_start: 200.2,
+            },
+            ChapterWarning::InvalidRange {
+                index: 4,
+                title: "Broken".to_string(),
+                start: 300.0,
+                end: 250.0,
+            },
+        ]
+    );
+    assert!(warnings[1].to_string().contains("01:30"), "{}", warnings[1]);
+}
+
+#[test]
+fn test_validate_flags_tiny_and_huge_tracks() {
+    let list = chapters(&[("Intro", 0.0, 2.0), ("Everything", 2.0, 3000.0)]);
+    let warnings = validate_chapters(&list, None);
+    assert!(matches!(
+        warnings[0],
+        ChapterWarning::TooShort { index: 0, .. }
+    ));
+    assert!(matches!(
+        warnings[1],
+        ChapterWarning::TooLong { index: 1, .. }
+    ));
+
+    // A single long chapter is just a long video
+    let single = chapters(&[("Live set", 0.0, 3000.0)]);
+    assert!(validate_chapters(&single, None).is_empty());
+}
+
+#[test]
+fn test_validate_compares_with_audio_duration() {
+    let list = chapters(&[("One", 0.0, 200.0), ("Two", 200.0, 400.0)]);
+    assert_eq!(
+        validate_chapters(&list, Some(300.0)),
+        vec![ChapterWarning::EndsAfterAudio {
+            chapter_end: 400.0,
+            audio_duration: 300.0,
+        }]
+    );
+    assert_eq!(
+        validate_chapters(&list, Some(180.0))[0],
+        ChapterWarning::PastAudioEnd {
+            count: 1,
+            audio_duration: 180.0,
+        }
+    );
+    assert_eq!(
+        validate_chapters(&list, Some(460.0)),
+        vec![ChapterWarning::EndsBeforeAudio {
+            chapter_end: 400.0,
+            audio_duration: 460.0,
+        }]
+    );
+    // Within the tolerance
+    assert!(validate_chapters(&list, Some(401.5)).is_empty());
+}
+
+fn spans(chapters: &[Chapter]) -> Vec<(&str, f64, f64)> {
+    chapters
+        .iter()
+        .map(|c| (c.title.as_str(), c.start_time, c.end_time))
+        .collect()
+}
+
+#[test]
+fn test_sanitize_fits_chapters_to_audio() {
+    let list = chapters(&[
+        ("One", 0.0, 200.0),
+        ("Bad", 250.0, 220.0),
+        ("Two", 200.0, 400.0),
+        ("Three", 400.0, 600.0),
+    ]);
+    let sanitized = sanitize_chapters(&list, Some(380.0)).unwrap();
+    assert_eq!(
+        spans(&sanitized),
+        vec![("One", 0.0, 200.0), ("Two", 200.0, 380.0)]
+    );
+    assert_eq!(sanitize_chapters(&list, None).unwrap().len(), 3);
+
+    assert!(sanitize_chapters(&list[..1], Some(0.5)).is_err());
+    assert!(sanitize_chapters(&list[1..2], None).is_err());
+}
+
+#[test]
+fn test_sanitize_drops_duplicates_and_overlaps() {
+    let list = chapters(&[
+        ("One", 0.0, 210.0),
+        ("Two", 200.0, 400.0),
+        ("Two again", 200.2, 390.0),
+        ("Three", 400.0, 600.0),
+    ]);
+    let sanitized = sanitize_chapters(&list, None).unwrap();
+    assert_eq!(
+        spans(&sanitized),
+        vec![
+            ("One", 0.0, 200.0),
+            ("Two", 200.0, 400.0),
+            ("Three", 400.0, 600.0)
+        ]
+    );
+}
+
+#[test]
+fn test_sanitize_rejects_out_of_order_chapters() {
+    let list = chapters(&[
+        ("One", 0.0, 200.0),
+        ("Two", 200.0, 400.0),
+        ("Chorus", 90.0, 120.0),
+    ]);
+    let err = sanitize_chapters(&list, Some(400.0))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Chorus"), "{}", err);
+    assert!(err.contains("--edit-chapters"), "{}", err);
+}
+
+#[test]
+fn test_sanitize_hands_out_of_order_chapters_to_the_editor_first() {
+    let list = chapters(&[
+        ("One", 0.0, 200.0),
+        ("Two", 200.0, 400.0),
+        ("Chorus", 90.0, 120.0),
+        ("Three", 400.0, 700.0),
+    ]);
+    let mut seen = Vec::new();
+    let fixed = sanitize_chapters_or_reorder(&list, Some(600.0), |given| {
+        seen = given.to_vec();
+        Ok(Some(chapters(&[
+            ("One", 0.0, 200.0),
+            ("Two", 200.0, 400.0),
+            ("Three", 400.0, 700.0),
+        ])))
+    })
+    .unwrap();
+    // The editor sees the list as it came, before anything is dropped.
+    let titles: Vec<&str> = seen.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, ["One", "Two", "Chorus", "Three"]);
+    // Its answer is sanitized like any other list.
+    assert_eq!(fixed.len(), 3);
+    assert_eq!(fixed[2].end_time, 600.0);
+
+    let err = sanitize_chapters_or_reorder(&list, Some(600.0), |_| Ok(None))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Chorus"), "{}", err);
+
+    let still_wrong =
+        sanitize_chapters_or_reorder(&list, Some(600.0), |given| Ok(Some(given.to_vec())));
+    assert!(still_wrong.is_err());
+
+    let in_order = sanitize_chapters_or_reorder(&fixed, Some(600.0), |_| {
+        panic!("the editor is only opened for out-of-order chapters")
+    });
+    assert_eq!(in_order.unwrap().len(), 3);
+}
@@ -0,0 +1,167 @@
+//! Tests for `audio::write_chaptered_file` / `audio::ffmetadata_document`:
+//! one file carrying the whole audio with embedded chapter markers.
+//!
+//! The round-trip test needs a local `ffmpeg`/`ffprobe` (no network); it is
+//! skipped when ffmpeg is not installed.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use youtube_chapter_splitter::{Chapter, audio};
+
+mod common;
+use common::ffmpeg_available;
+
+fn test_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("ytcs_chaptered_{}", name));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    root
+}
+
+#[test]
+fn test_ffmetadata_document_escapes_special_characters() {
+    let chapters = vec![
+        Chapter::new("A=B; C#1".to_string(), 0.0, 1.5),
+        Chapter::new("Back\\slash".to_string(), 1.5, 3.0),
+    ];
+    let doc = audio::ffmetadata_document(&[("album", "Live; Vol. 1")], &chapters);
+
+    assert!(doc.starts_with(";FFMETADATA1\n"));
+    assert!(doc.contains("album=Live\\; Vol. 1\n"));
+    assert!(doc.contains("title=A\\=B\\; C\\#1\n"));
+    assert!(doc.contains("title=Back\\\\slash\n"));
+    assert!(doc.contains("START=1500\nEND=3000\n"));
+    assert_eq!(doc.matches("[CHAPTER]").count(), 2);
+}
+
+#[test]
+fn test_write_chaptered_file_refuses_existing_target_without_overwrite() {
+    let root = test_root("existing");
+    let target = root.join("Artist - Album (full).mp3");
+    fs::write(&target, b"previous run").unwrap();
+
+    let chapters = vec![Chapter::new("Only".to_string(), 0.0, 2.0)];
+    let result = audio::write_chaptered_file(
+        &root.join("source.mp3"),
+        &chapters,
+        &target,
+        "Album",
+        "Artist",
+        "Album",
+        None,
+        192,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(fs::read(&target).unwrap(), b"previous run");
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_write_chaptered_file_embeds_chapters() {
+    if !ffmpeg_available() {
+        eprintln!("Skipping: ffmpeg not available");
+        return;
+    }
+    let root = test_root("roundtrip");
+    let source = root.join("temp_audio.mp3");
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            "anullsrc=r=8000:cl=mono",
+            "-t",
+            "6",
+            "-c:a",
+            "libmp3lame",
+            source.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success());
+
+    let chapters = vec![
+        Chapter::new("One".to_string(), 0.0, 2.0),
+        Chapter::new("Two".to_string(), 2.0, 4.0),
+        Chapter::new("Three".to_string(), 4.0, 6.0),
+    ];
+    let target = root.join("Artist - Album (full).mp3");
+    audio::write_chaptered_file(
+        &source, &chapters, &target, "Album", "Artist", "Album", None, 192, None, None, None, false,
+    )
+    .unwrap();
+
+    let probe = Command::new("ffprobe")
+        .args(["-v", "error", "-show_chapters", "-of", "json"])
+        .arg(&target)
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&probe.stdout).unwrap();
+    assert_eq!(json["chapters"].as_array().map(Vec::len), Some(3));
+    assert!(source.exists(), "the source file must be left untouched");
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_write_chaptered_file_reencodes_mp3_into_m4b() {
+    if !ffmpeg_available() {
+        eprintln!("Skipping: ffmpeg not available");
+        return;
+    }
+    let root = test_root("m4b");
+    let source = root.join("temp_audio.mp3");
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            "anullsrc=r=44100:cl=mono",
+            "-t",
+            "4",
+            "-c:a",
+            "libmp3lame",
+            source.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success());
+
+    let chapters = vec![
+        Chapter::new("Part 1".to_string(), 0.0, 2.0),
+        Chapter::new("Part 2".to_string(), 2.0, 4.0),
+    ];
+    let target = root.join("Author - Book.m4b");
+    audio::write_chaptered_file(
+        &source, &chapters, &target, "Book", "Author", "Book", None, 128, None, None, None, false,
+    )
+    .unwrap();
+
+    let probe = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_chapters",
+            "-show_streams",
+            "-of",
+            "json",
+        ])
+        .arg(&target)
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&probe.stdout).unwrap();
+    assert_eq!(json["chapters"].as_array().map(Vec::len), Some(2));
+    assert_eq!(json["streams"][0]["codec_name"], "aac");
+
+    let _ = fs::remove_dir_all(&root);
+}
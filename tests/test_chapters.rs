@@ -1,4 +1,6 @@
-use youtube_chapter_splitter::chapters::{Chapter, format_timestamp, parse_timestamp};
+use youtube_chapter_splitter::chapters::{
+    Chapter, format_timestamp, parse_chapters_from_json, parse_timestamp,
+};
 
 #[test]
 fn test_chapter_creation() {
@@ -65,3 +67,22 @@ fn test_chapter_serialization() {
     assert_eq!(chapter.start_time, deserialized.start_time);
     assert_eq!(chapter.end_time, deserialized.end_time);
 }
+
+#[test]
+fn test_chapter_try_new_rejects_bad_ranges() {
+    assert!(Chapter::try_new("Ok".to_string(), 0.0, 1.0).is_ok());
+    assert!(Chapter::try_new("Backwards".to_string(), 10.0, 5.0).is_err());
+    assert!(Chapter::try_new("Empty".to_string(), 5.0, 5.0).is_err());
+    assert!(Chapter::try_new("Negative".to_string(), -1.0, 5.0).is_err());
+    assert!(Chapter::try_new("NaN".to_string(), f64::NAN, 5.0).is_err());
+}
+
+#[test]
+fn test_parse_chapters_from_json_rejects_backwards_chapter() {
+    let json = r#"{"chapters": [
+        {"title": "One", "start_time": 0.0, "end_time": 60.0},
+        {"title": "Two", "start_time": 60.0, "end_time": 30.0}
+    ]}"#;
+    let err = parse_chapters_from_json(json).unwrap_err().to_string();
+    assert!(err.contains("Two"), "{}", err);
+}
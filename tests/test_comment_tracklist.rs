@@ -0,0 +1,76 @@
+use youtube_chapter_splitter::comment_tracklist::*;
+
+fn comment(text: &str, pinned: bool, likes: u64) -> Comment {
+    Comment {
+        text: text.to_string(),
+        author: Some("@listener".to_string()),
+        is_pinned: pinned,
+        like_count: Some(likes),
+        ..Default::default()
+    }
+}
+
+const TRACKLIST: &str = "Tracklist:\n0:00 Intro\n4:10 Second Song\n9:30 Third Song\n15:00 Closer";
+
+#[test]
+fn test_parse_comments_skips_replies() {
+    let json = r#"{
+        "title": "Album",
+        "comments": [
+            {"id": "a", "parent": "root", "text": "0:00 One", "author": "@x", "is_pinned": true, "like_count": 12},
+            {"id": "b", "parent": "a", "text": "thanks!", "author": "@y"},
+            {"id": "c", "text": "great", "author_is_uploader": true}
+        ]
+    }"#;
+    let comments = parse_comments(json).unwrap();
+    assert_eq!(comments.len(), 2);
+    assert!(comments[0].is_pinned);
+    assert_eq!(comments[0].like_count, Some(12));
+    assert!(comments[1].author_is_uploader);
+
+    assert!(parse_comments(r#"{"title": "x"}"#).unwrap().is_empty());
+    assert!(parse_comments(r#"{"comments": null}"#).unwrap().is_empty());
+    assert!(parse_comments("not json").is_err());
+}
+
+#[test]
+fn test_best_tracklist_prefers_coverage() {
+    let partial = "0:00 Intro\n2:00 Second Song\n4:00 Third Song\n6:00 Fourth";
+    let comments = vec![comment(partial, true, 500), comment(TRACKLIST, false, 3)];
+    let best = best_tracklist(&comments, 20.0 * 60.0).unwrap();
+    assert_eq!(best.chapters.len(), 4);
+    assert_eq!(best.chapters[3].title, "Closer");
+    assert_eq!(best.chapters[3].end_time, 1200.0);
+    assert!(!best.pinned);
+    assert!(best.coverage > 0.9, "{}", best.coverage);
+}
+
+#[test]
+fn test_best_tracklist_ties_go_to_pinned_then_likes() {
+    let liked = Comment {
+        author: Some("@fan".to_string()),
+        ..comment(TRACKLIST, false, 80)
+    };
+    let comments = vec![
+        comment(TRACKLIST, false, 50),
+        liked,
+        comment(TRACKLIST, true, 1),
+    ];
+    let best = best_tracklist(&comments, 20.0 * 60.0).unwrap();
+    assert!(best.pinned);
+
+    let best = best_tracklist(&comments[..2], 20.0 * 60.0).unwrap();
+    assert!(!best.pinned);
+    assert_eq!(best.author.as_deref(), Some("@fan"));
+}
+
+#[test]
+fn test_best_tracklist_ignores_remarks() {
+    let comments = vec![
+        comment("2:31 and 14:02 are the best parts", false, 900),
+        comment("1:00 wow\n1:30 chills\n2:00 the drop", false, 40),
+        comment("what a record", true, 10),
+    ];
+    assert!(best_tracklist(&comments, 20.0 * 60.0).is_none());
+    assert!(best_tracklist(&[], 600.0).is_none());
+}
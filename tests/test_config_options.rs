@@ -1,4 +1,6 @@
-use youtube_chapter_splitter::config::{AudioFormat, Config};
+use youtube_chapter_splitter::config::{
+    AudioFormat, ChapteredFormat, Config, OutputMode, RefineStrategy,
+};
 
 #[test]
 fn test_config_default_values() {
@@ -9,9 +11,20 @@ fn test_config_default_values() {
     assert_eq!(config.max_retries, 3);
     assert!(!config.create_playlist);
     assert!(config.refine_chapters);
+    assert!(config.refine_manifest);
+    assert_eq!(config.refine_strategy, RefineStrategy::Silence);
     assert_eq!(config.audio_format, AudioFormat::Mp3);
     assert!(!config.playlist_prefix_index);
     assert!(config.download_cover);
+    assert!(config.channel_as_artist);
+    assert!(!config.album_playlists);
+    assert!(!config.review_chapters);
+    assert_eq!(config.silence_noise_db, -30.0);
+    assert_eq!(config.silence_min_duration, 2.0);
+    assert_eq!(config.min_track_duration, 10.0);
+    assert_eq!(config.max_tracks, 100);
+    assert!(!config.comment_tracklists);
+    assert_eq!(config.max_comments, 20);
 }
 
 #[test]
@@ -101,6 +114,44 @@ fn test_config_format_directory() {
     assert_eq!(result, "Marigold - Oblivion Gate");
 }
 
+#[test]
+fn test_config_format_directory_release_year() {
+    let config = Config {
+        directory_format: "%a - %y - %A".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        config.format_directory_with_year("Pink Floyd", "Dark Side", Some(1973)),
+        "Pink Floyd - 1973 - Dark Side"
+    );
+    assert_eq!(
+        config.format_directory_with_year("Pink Floyd", "Dark Side", None),
+        "Pink Floyd - Dark Side"
+    );
+
+    let config = Config {
+        directory_format: "%a - %A (%y)".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        config.format_directory_with_year("Pink Floyd", "Dark Side", Some(1973)),
+        "Pink Floyd - Dark Side (1973)"
+    );
+    assert_eq!(
+        config.format_directory("Pink Floyd", "Dark Side"),
+        "Pink Floyd - Dark Side"
+    );
+
+    let config = Config {
+        directory_format: "%y - %A".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        config.format_directory("Pink Floyd", "Dark Side"),
+        "Dark Side"
+    );
+}
+
 #[test]
 fn test_config_format_directory_windows_forbidden_chars() {
     let config = Config::default();
@@ -172,3 +223,60 @@ fn test_config_deserialization() {
     assert!(config.create_playlist);
     assert!(config.refine_chapters);
 }
+
+#[test]
+fn test_config_keep_source_audio_defaults() {
+    let config = Config::default();
+    assert!(!config.keep_source_audio);
+    assert_eq!(
+        config.format_source_audio_name("Marigold", "Oblivion Gate"),
+        "Marigold - Oblivion Gate (full)"
+    );
+}
+
+#[test]
+fn test_config_format_source_audio_name_sanitizes_placeholders() {
+    let config = Config {
+        source_audio_format: "%A - complete".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        config.format_source_audio_name("Artist", "Live: Vol/1"),
+        "Live_ Vol_1 - complete"
+    );
+}
+
+#[test]
+fn test_config_single_file_output_mode_from_toml() {
+    let config: Config = toml::from_str(
+        r#"
+output_mode = "single_file"
+single_file_format = "mka"
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.output_mode, OutputMode::SingleFile);
+    assert_eq!(config.single_file_format, ChapteredFormat::Mka);
+    assert_eq!(config.single_file_format.extension(), "mka");
+
+    let default = Config::default();
+    assert_eq!(default.output_mode, OutputMode::Tracks);
+    assert_eq!(default.single_file_format.extension(), "m4b");
+}
+
+#[test]
+fn test_config_title_rules_round_trip() {
+    use youtube_chapter_splitter::title_rules::{CaseStyle, ReplaceRule};
+
+    let mut config = Config::default();
+    assert!(!toml::to_string(&config).unwrap().contains("title_rules"));
+
+    config.title_rules.case = Some(CaseStyle::Sentence);
+    config.title_rules.replace.push(ReplaceRule {
+        pattern: "&".to_string(),
+        with: "and".to_string(),
+    });
+    let toml_str = toml::to_string_pretty(&config).unwrap();
+    let back: Config = toml::from_str(&toml_str).unwrap();
+    assert_eq!(back.title_rules, config.title_rules);
+}
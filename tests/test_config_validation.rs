@@ -86,6 +86,95 @@ fn test_validate_rejects_path_separator_in_directory_format() {
     assert!(config.validate().is_err());
 }
 
+#[test]
+fn test_validate_rejects_path_separator_in_source_audio_format() {
+    let config = Config {
+        source_audio_format: "full/%a - %A".to_string(),
+        ..valid_config()
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_validate_rejects_zero_parallelism() {
+    let config = Config {
+        parallel_downloads: 0,
+        ..valid_config()
+    };
+    assert!(config.validate().is_err());
+
+    let config = Config {
+        parallel_splits: 0,
+        ..valid_config()
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_validate_rejects_bad_silence_detection_settings() {
+    let bad = [
+        Config {
+            silence_noise_db: 5.0,
+            ..valid_config()
+        },
+        Config {
+            silence_min_duration: 0.0,
+            ..valid_config()
+        },
+        Config {
+            min_track_duration: -1.0,
+            ..valid_config()
+        },
+        Config {
+            max_tracks: 0,
+            ..valid_config()
+        },
+    ];
+    for config in bad {
+        assert!(config.validate().is_err());
+    }
+    let no_merging = Config {
+        min_track_duration: 0.0,
+        ..valid_config()
+    };
+    assert!(no_merging.validate().is_ok());
+}
+
+#[test]
+fn test_validate_rejects_bad_refine_limits() {
+    for config in [
+        Config {
+            refine_max_delta: -1.0,
+            ..valid_config()
+        },
+        Config {
+            refine_min_confidence: 1.5,
+            ..valid_config()
+        },
+        Config {
+            refine_min_confidence: f64::NAN,
+            ..valid_config()
+        },
+    ] {
+        assert!(config.validate().is_err());
+    }
+    let config = Config {
+        refine_max_delta: 2.0,
+        refine_min_confidence: 0.6,
+        ..valid_config()
+    };
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_validate_rejects_zero_max_comments() {
+    let config = Config {
+        max_comments: 0,
+        ..valid_config()
+    };
+    assert!(config.validate().is_err());
+}
+
 #[test]
 fn test_load_rejects_hand_edited_invalid_config() {
     let toml_str = r#"
@@ -94,3 +183,32 @@ fn test_load_rejects_hand_edited_invalid_config() {
     let config: Config = toml::from_str(toml_str).unwrap();
     assert!(config.validate().is_err());
 }
+
+#[test]
+fn test_validate_rejects_invalid_title_rule_pattern() {
+    let config: Config = toml::from_str(
+        r#"
+[title_rules]
+strip = ["(unclosed"]
+"#,
+    )
+    .unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("title_rules"), "{}", err);
+}
+
+#[test]
+fn test_validate_rejects_invalid_chapter_exclude_pattern() {
+    let config: Config = toml::from_str("chapter_exclude = \"^(intro\"\n").unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("^(intro"), "{}", err);
+
+    let config: Config =
+        toml::from_str("chapter_exclude = \"^(intro|outro)\"\nexcluded_chapters = \"merge\"\n")
+            .unwrap();
+    assert!(config.validate().is_ok());
+    assert_eq!(
+        config.excluded_chapters,
+        youtube_chapter_splitter::ExcludedChapters::Merge
+    );
+}
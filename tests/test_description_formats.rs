@@ -0,0 +1,181 @@
+// Tests for the additional description tracklist layouts: trailing times,
+// time ranges, numbered lists, duration-only and untimed tracklists.
+
+use youtube_chapter_splitter::chapters_from_description::{
+    parse_chapters_from_description, parse_untimed_tracklist,
+};
+
+fn starts_and_titles(description: &str, duration: f64) -> Vec<(f64, String)> {
+    parse_chapters_from_description(description, duration)
+        .unwrap()
+        .into_iter()
+        .map(|c| (c.start_time, c.title))
+        .collect()
+}
+
+#[test]
+fn test_trailing_timestamp_format() {
+    let description = r#"
+Tracklist
+Sunrise Over Water - 0:00
+Midnight Engines - 3:45
+Paper Lanterns – 8:02
+"#;
+    assert_eq!(
+        starts_and_titles(description, 720.0),
+        vec![
+            (0.0, "Sunrise Over Water".to_string()),
+            (225.0, "Midnight Engines".to_string()),
+            (482.0, "Paper Lanterns".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_time_range_format() {
+    let description = r#"
+00:00 - 03:45 Sunrise Over Water
+03:45 - 08:02 Midnight Engines
+08:02 - 12:00 Paper Lanterns
+"#;
+    assert_eq!(
+        starts_and_titles(description, 720.0),
+        vec![
+            (0.0, "Sunrise Over Water".to_string()),
+            (225.0, "Midnight Engines".to_string()),
+            (482.0, "Paper Lanterns".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_numbered_trailing_format() {
+    let description = r#"
+01. Sunrise Over Water 0:00
+02. Midnight Engines 3:45
+03. Paper Lanterns 8:02
+"#;
+    let chapters = starts_and_titles(description, 720.0);
+    assert_eq!(chapters[0], (0.0, "Sunrise Over Water".to_string()));
+    assert_eq!(chapters[2], (482.0, "Paper Lanterns".to_string()));
+}
+
+#[test]
+fn test_duration_only_tracklist_accumulates_starts() {
+    let description = r#"
+1. Sunrise Over Water (4:12)
+2. Midnight Engines (3:30)
+3. Paper Lanterns (5:18)
+"#;
+    // 4:12 + 3:30 + 5:18 = 13:00
+    assert_eq!(
+        starts_and_titles(description, 780.0),
+        vec![
+            (0.0, "Sunrise Over Water".to_string()),
+            (252.0, "Midnight Engines".to_string()),
+            (462.0, "Paper Lanterns".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_increasing_lengths_read_as_durations_when_they_add_up() {
+    // Increasing values could be start times, but the first track would then
+    // start three minutes in, while as lengths they add up to the video.
+    let description = r#"
+1. Short One (3:00)
+2. Longer Two (4:00)
+3. Longest Three (5:00)
+"#;
+    let starts: Vec<f64> = starts_and_titles(description, 720.0)
+        .into_iter()
+        .map(|(s, _)| s)
+        .collect();
+    assert_eq!(starts, vec![0.0, 180.0, 420.0]);
+}
+
+#[test]
+fn test_durations_far_from_video_length_are_rejected() {
+    let description = r#"
+1. Sunrise Over Water (4:12)
+2. Midnight Engines (3:30)
+"#;
+    // Lengths sum to 7:42 but the video lasts an hour, and read as start
+    // times they are not increasing.
+    assert!(parse_chapters_from_description(description, 3600.0).is_err());
+}
+
+#[test]
+fn test_format_reading_most_lines_wins() {
+    // Two stray leading-timestamp lines don't outvote four trailing ones.
+    let description = r#"
+12:00 Doors open
+13:30 Q&A
+Opening - 0:00
+Second Song - 4:10
+Third Song - 9:00
+Encore - 14:20
+"#;
+    let titles: Vec<String> = starts_and_titles(description, 1200.0)
+        .into_iter()
+        .map(|(_, t)| t)
+        .collect();
+    assert_eq!(
+        titles,
+        vec!["Opening", "Second Song", "Third Song", "Encore"]
+    );
+}
+
+#[test]
+fn test_untimed_numbered_tracklist() {
+    let description = r#"
+Full album, remastered in 2019.
+
+1. Sunrise Over Water
+2) Midnight Engines (4:12)
+03 - Paper Lanterns
+
+4. Glass Harbor
+Buy it here: https://example.com/album
+"#;
+    assert_eq!(
+        parse_untimed_tracklist(description).unwrap(),
+        vec![
+            "Sunrise Over Water",
+            "Midnight Engines",
+            "Paper Lanterns",
+            "Glass Harbor"
+        ]
+    );
+}
+
+#[test]
+fn test_untimed_vinyl_sides_and_headed_list() {
+    let sides = "A1 First\nA2 Second\nB1 Third\nB2 Fourth\nB4 Skipped";
+    assert_eq!(
+        parse_untimed_tracklist(sides).unwrap(),
+        vec!["First", "Second", "Third", "Fourth"]
+    );
+
+    let headed = "Recorded live.\n\nSetlist:\n- Opening\n- Middle Song\n- Closing\n\nThanks!";
+    assert_eq!(
+        parse_untimed_tracklist(headed).unwrap(),
+        vec!["Opening", "Middle Song", "Closing"]
+    );
+}
+
+#[test]
+fn test_untimed_tracklist_needs_a_real_sequence() {
+    for description in [
+        "1. Like the video\n2. Subscribe",
+        "1. Intro\n3. Skipped a number\n4. Again",
+        "Top 3 albums of 2020\n5 stars from me",
+        "Tracklist:\nOnly one\n\nsomething else",
+    ] {
+        assert!(
+            parse_untimed_tracklist(description).is_none(),
+            "{:?}",
+            description
+        );
+    }
+}
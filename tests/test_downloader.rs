@@ -1,5 +1,9 @@
 use std::fs;
-use youtube_chapter_splitter::downloader::{download_thumbnail, extract_video_id};
+use youtube_chapter_splitter::downloader::{
+    download_thumbnail, extract_video_id, parse_video_info,
+};
+use youtube_chapter_splitter::ui::MetadataSource;
+use youtube_chapter_splitter::utils::artist_album_from_info;
 
 #[test]
 fn test_extract_video_id_standard() {
@@ -54,3 +58,97 @@ fn download_thumbnail_reachability() {
     assert!(r.unwrap().exists());
     let _ = fs::remove_dir_all(&dir);
 }
+
+#[test]
+fn test_parse_video_info_non_youtube_site() {
+    let json = r#"{
+        "id": "live-at-the-hall",
+        "extractor_key": "Bandcamp",
+        "title": "Live at the Hall",
+        "duration": 600.0,
+        "thumbnail": "https://f4.bcbits.com/img/a123_10.jpg",
+        "chapters": [
+            {"title": "Opening", "start_time": 0.0, "end_time": 300.0},
+            {"title": "Closing", "start_time": 300.0, "end_time": 600.0}
+        ]
+    }"#;
+    let info = parse_video_info(json).unwrap();
+    assert_eq!(info.media.extractor, "bandcamp");
+    assert!(!info.is_youtube());
+    assert_eq!(info.media.id, "live-at-the-hall");
+    assert_eq!(info.chapters.len(), 2);
+    assert_eq!(
+        info.thumbnail.as_deref(),
+        Some("https://f4.bcbits.com/img/a123_10.jpg")
+    );
+}
+
+#[test]
+fn test_parse_video_info_rejects_one_line_per_track() {
+    let json = concat!(
+        r#"{"id": "1", "extractor_key": "Bandcamp", "title": "Opening", "playlist_title": "Live at the Hall"}"#,
+        "\n",
+        r#"{"id": "2", "extractor_key": "Bandcamp", "title": "Closing", "playlist_title": "Live at the Hall"}"#,
+        "\n",
+    );
+    let err = parse_video_info(json).unwrap_err().to_string();
+    assert!(
+        err.contains("\"Live at the Hall\" lists 2 tracks"),
+        "{}",
+        err
+    );
+    assert!(err.contains("--as-album"), "{}", err);
+}
+
+#[test]
+fn test_parse_video_info_youtube() {
+    let json =
+        r#"{"id": "dQw4w9WgXcQ", "extractor_key": "Youtube", "title": "A - B", "duration": 10}"#;
+    let info = parse_video_info(json).unwrap();
+    assert!(info.is_youtube());
+    assert!(info.chapters.is_empty());
+}
+
+#[test]
+fn test_parse_video_info_youtube_music_metadata() {
+    let json = r#"{
+        "id": "dQw4w9WgXcQ",
+        "extractor_key": "Youtube",
+        "title": "Time",
+        "duration": 413,
+        "upload_date": "20150803",
+        "channel": "Pink Floyd - Topic",
+        "artists": ["Pink Floyd"],
+        "artist": "Pink Floyd",
+        "album": "The Dark Side of the Moon",
+        "track": "Time",
+        "release_year": 1973
+    }"#;
+    let info = parse_video_info(json).unwrap();
+    assert_eq!(info.music.artist.as_deref(), Some("Pink Floyd"));
+    assert_eq!(
+        info.music.album.as_deref(),
+        Some("The Dark Side of the Moon")
+    );
+    assert_eq!(info.music.track.as_deref(), Some("Time"));
+    assert_eq!(info.music.release_year, Some(1973));
+    assert_eq!(info.tag_date().as_deref(), Some("1973"));
+
+    let ((artist, album), artist_src, album_src) = artist_album_from_info(&info);
+    assert_eq!(artist, "Pink Floyd");
+    assert_eq!(album, "The Dark Side Of The Moon");
+    assert_eq!(artist_src, MetadataSource::Platform);
+    assert_eq!(album_src, MetadataSource::Platform);
+}
+
+#[test]
+fn test_artist_album_from_info_falls_back_to_title() {
+    let json = r#"{"id": "dQw4w9WgXcQ", "title": "Marigold - Oblivion Gate (Full Album)", "upload_date": "20240102", "album": "Oblivion Gate"}"#;
+    let info = parse_video_info(json).unwrap();
+    let ((artist, album), artist_src, album_src) = artist_album_from_info(&info);
+    assert_eq!(artist, "Marigold");
+    assert_eq!(album, "Oblivion Gate");
+    assert_eq!(artist_src, MetadataSource::Detected);
+    assert_eq!(album_src, MetadataSource::Platform);
+    assert_eq!(info.tag_date().as_deref(), Some("2024-01-02"));
+}
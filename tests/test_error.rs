@@ -97,3 +97,25 @@ fn test_missing_tools_error_tools_to_install() {
         vec!["deno"]
     );
 }
+
+#[test]
+fn test_error_exit_codes() {
+    assert_eq!(YtcsError::Other("x".to_string()).exit_code(), 1);
+    assert_eq!(YtcsError::InputRequired("x".to_string()).exit_code(), 2);
+    assert_eq!(
+        YtcsError::BatchIncomplete {
+            failed: 2,
+            total: 5
+        }
+        .exit_code(),
+        3
+    );
+    assert_eq!(
+        YtcsError::BatchIncomplete {
+            failed: 5,
+            total: 5
+        }
+        .exit_code(),
+        1
+    );
+}
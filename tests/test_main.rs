@@ -59,7 +59,34 @@ fn test_binary_rejects_non_youtube_url_with_generic_error() {
 
     assert_eq!(output.status.code(), Some(1));
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("Invalid YouTube URL") || stderr.contains("Unable to extract"));
+    assert!(stderr.contains("Invalid URL") || stderr.contains("Unable to extract"));
+}
+
+#[test]
+fn test_binary_reports_unreadable_batch_file() {
+    // The list is read before any tool or network access.
+    let output = ytcs_command()
+        .args(["--batch-file", "/nonexistent/ytcs-urls.txt"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Cannot read batch file"), "{}", stderr);
+}
+
+#[test]
+fn test_binary_rejects_edit_chapters_with_stdin_batch() {
+    // $EDITOR can't share stdin with the piped URL list.
+    let output = ytcs_command()
+        .args(["--batch-file", "-", "--edit-chapters"])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--edit-chapters"), "{}", stderr);
 }
 
 /// Points `dirs::config_dir()` (and therefore `Config::config_path()`) at an
@@ -111,3 +138,53 @@ fn test_binary_non_interactive_playlist_ask_exits_with_code_2() {
 
     let _ = std::fs::remove_dir_all(&config_home);
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_binary_test_title_applies_title_rules() {
+    let config_home = isolated_config_home("test_title");
+    std::fs::write(
+        config_home.join("ytcs/config.toml"),
+        r#"directory_format = "%a - %y - %A"
+
+[title_rules]
+small_words = ["of", "the"]
+strip = ['\s*\(remastered\)']
+
+[[title_rules.replace]]
+pattern = '\bfeat\.'
+with = "ft."
+"#,
+    )
+    .unwrap();
+
+    // Pure string processing: no yt-dlp, ffmpeg or network involved.
+    let output = ytcs_command()
+        .args([
+            "test-title",
+            "PINK FLOYD - dark side of the moon (Remastered) [1973]",
+        ])
+        .env("XDG_CONFIG_HOME", &config_home)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("artist    Pink Floyd (detected)"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("album     Dark Side of the Moon"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("folder    Pink Floyd - 1973 - Dark Side of the Moon"),
+        "{}",
+        stdout
+    );
+
+    let _ = std::fs::remove_dir_all(&config_home);
+}
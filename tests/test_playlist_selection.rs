@@ -0,0 +1,123 @@
+use youtube_chapter_splitter::playlist::PlaylistVideo;
+use youtube_chapter_splitter::playlist_selection::*;
+
+fn video(n: usize, title: &str, duration: f64) -> PlaylistVideo {
+    PlaylistVideo {
+        id: format!("video{:06}", n),
+        title: title.to_string(),
+        url: format!("https://www.youtube.com/watch?v=video{:06}", n),
+        duration,
+        channel: None,
+    }
+}
+
+fn sample() -> Vec<PlaylistVideo> {
+    vec![
+        video(1, "Intro", 45.0),
+        video(2, "Song One (Official Video)", 240.0),
+        video(3, "Song Two", 200.0),
+        video(4, "Song Three (Live)", 3600.0),
+        video(5, "Trailer", 0.0),
+    ]
+}
+
+fn titles(videos: &[PlaylistVideo]) -> Vec<&str> {
+    videos.iter().map(|v| v.title.as_str()).collect()
+}
+
+#[test]
+fn test_parse_item_ranges() {
+    let ranges = parse_item_ranges("1-3, 5,8-").unwrap();
+    assert_eq!(
+        ranges,
+        vec![
+            ItemRange {
+                start: 1,
+                end: Some(3)
+            },
+            ItemRange {
+                start: 5,
+                end: Some(5)
+            },
+            ItemRange {
+                start: 8,
+                end: None
+            },
+        ]
+    );
+    assert_eq!(
+        parse_item_ranges("-2").unwrap(),
+        vec![ItemRange {
+            start: 1,
+            end: Some(2)
+        }]
+    );
+}
+
+#[test]
+fn test_parse_item_ranges_rejects_invalid_specs() {
+    for spec in ["", "0", "5-2", "a-b", "1,,x", "1-2-3"] {
+        assert!(parse_item_ranges(spec).is_err(), "{:?} should fail", spec);
+    }
+}
+
+#[test]
+fn test_selection_items_keep_playlist_order() {
+    let selection = PlaylistSelection {
+        items: Some(parse_item_ranges("4,1-2").unwrap()),
+        ..Default::default()
+    };
+    assert_eq!(
+        titles(&selection.apply(&sample())),
+        vec!["Intro", "Song One (Official Video)", "Song Three (Live)"]
+    );
+}
+
+#[test]
+fn test_selection_title_filters_are_case_insensitive() {
+    let selection = PlaylistSelection {
+        match_title: Some(title_pattern("^song").unwrap()),
+        reject_title: Some(title_pattern("\\(live\\)").unwrap()),
+        ..Default::default()
+    };
+    assert_eq!(
+        titles(&selection.apply(&sample())),
+        vec!["Song One (Official Video)", "Song Two"]
+    );
+    assert!(title_pattern("(unclosed").is_err());
+}
+
+#[test]
+fn test_selection_duration_window_keeps_unknown_durations() {
+    let selection = PlaylistSelection {
+        min_duration: Some(parse_duration_limit("1:00").unwrap()),
+        max_duration: Some(parse_duration_limit("30:00").unwrap()),
+        ..Default::default()
+    };
+    assert_eq!(
+        titles(&selection.apply(&sample())),
+        vec!["Song One (Official Video)", "Song Two", "Trailer"]
+    );
+    assert!(parse_duration_limit("soon").is_err());
+}
+
+#[test]
+fn test_selection_reverse_after_filters() {
+    let selection = PlaylistSelection {
+        items: Some(parse_item_ranges("2-").unwrap()),
+        reverse: true,
+        ..Default::default()
+    };
+    assert!(!selection.is_all());
+    assert_eq!(
+        titles(&selection.apply(&sample())),
+        vec![
+            "Trailer",
+            "Song Three (Live)",
+            "Song Two",
+            "Song One (Official Video)"
+        ]
+    );
+    assert!(PlaylistSelection::default().is_all());
+    assert_eq!(PlaylistSelection::default().apply(&sample()).len(), 5);
+}
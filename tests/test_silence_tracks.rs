@@ -0,0 +1,135 @@
+use std::process::Command;
+use youtube_chapter_splitter::audio::{
+    SilenceCandidate, detect_silence_chapters_for_count, merge_short_tracks, pick_track_cuts,
+};
+use youtube_chapter_splitter::chapters::Chapter;
+
+mod common;
+use common::ffmpeg_available;
+
+fn silence(start: f64, end: f64, level: usize) -> SilenceCandidate {
+    SilenceCandidate { start, end, level }
+}
+
+#[test]
+fn test_pick_track_cuts_prefers_strict_then_long_silences() {
+    let candidates = vec![
+        silence(59.0, 61.0, 0),
+        silence(119.5, 120.5, 2),
+        silence(179.0, 181.0, 1),
+        silence(89.8, 90.2, 2),
+    ];
+    assert_eq!(
+        pick_track_cuts(&candidates, 240.0, 3, 10.0),
+        Some(vec![60.0, 180.0])
+    );
+    assert_eq!(
+        pick_track_cuts(&candidates, 240.0, 4, 10.0),
+        Some(vec![60.0, 120.0, 180.0])
+    );
+    assert_eq!(
+        pick_track_cuts(&candidates, 240.0, 5, 10.0),
+        Some(vec![60.0, 90.0, 120.0, 180.0])
+    );
+    assert_eq!(pick_track_cuts(&candidates, 240.0, 6, 10.0), None);
+    assert_eq!(pick_track_cuts(&candidates, 240.0, 1, 10.0), Some(vec![]));
+    assert_eq!(pick_track_cuts(&candidates, 240.0, 0, 10.0), None);
+}
+
+#[test]
+fn test_pick_track_cuts_keeps_tracks_apart() {
+    let candidates = vec![
+        silence(2.0, 4.0, 0),
+        silence(60.0, 62.0, 0),
+        silence(64.0, 65.0, 0),
+        silence(235.0, 239.0, 0),
+        silence(150.0, 150.5, 1),
+    ];
+    // Lead-in, a cut 3.5s after another and the fade-out gap are unusable
+    assert_eq!(
+        pick_track_cuts(&candidates, 240.0, 3, 10.0),
+        Some(vec![61.0, 150.25])
+    );
+    assert_eq!(pick_track_cuts(&candidates, 240.0, 4, 10.0), None);
+}
+
+#[test]
+fn test_pick_track_cuts_min_track_is_configurable() {
+    let candidates = vec![silence(59.0, 61.0, 0), silence(74.0, 76.0, 0)];
+    assert_eq!(pick_track_cuts(&candidates, 240.0, 3, 20.0), None);
+    assert_eq!(
+        pick_track_cuts(&candidates, 240.0, 3, 10.0),
+        Some(vec![60.0, 75.0])
+    );
+}
+
+fn tracks(bounds: &[f64]) -> Vec<Chapter> {
+    bounds
+        .windows(2)
+        .enumerate()
+        .map(|(i, w)| Chapter::new(format!("Track {}", i + 1), w[0], w[1]))
+        .collect()
+}
+
+fn spans(chapters: &[Chapter]) -> Vec<(String, f64, f64)> {
+    chapters
+        .iter()
+        .map(|c| (c.title.clone(), c.start_time, c.end_time))
+        .collect()
+}
+
+#[test]
+fn test_merge_short_tracks_joins_shorter_neighbour() {
+    // 3s quiet passage between a 60s and a 200s track, a 2s lead-in
+    let chapters = tracks(&[0.0, 2.0, 62.0, 65.0, 265.0]);
+    assert_eq!(
+        spans(&merge_short_tracks(&chapters, 10.0)),
+        vec![
+            ("Track 1".to_string(), 0.0, 65.0),
+            ("Track 2".to_string(), 65.0, 265.0)
+        ]
+    );
+    assert_eq!(merge_short_tracks(&chapters, 0.0).len(), 4);
+}
+
+#[test]
+fn test_merge_short_tracks_keeps_one_track() {
+    let chapters = tracks(&[0.0, 3.0, 5.0, 8.0]);
+    assert_eq!(
+        spans(&merge_short_tracks(&chapters, 10.0)),
+        vec![("Track 1".to_string(), 0.0, 8.0)]
+    );
+}
+
+#[test]
+fn test_detect_silence_chapters_for_count() {
+    if !ffmpeg_available() {
+        eprintln!("Skipping: ffmpeg not available");
+        return;
+    }
+    let dir = std::env::temp_dir().join(format!("ytcs_silence_tracks_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let audio = dir.join("album.wav");
+    // Three 20s tones separated by 2s of silence, each with a short quiet dip
+    let status = Command::new("ffmpeg")
+        .args([
+            "-f",
+            "lavfi",
+            "-i",
+            "aevalsrc='if(lt(mod(t,22),20),if(between(mod(t,22),10,10.4),0.005,1)*sin(440*2*PI*t),0)':d=66",
+            "-y",
+        ])
+        .arg(&audio)
+        .output()
+        .unwrap();
+    assert!(status.status.success());
+
+    let chapters = detect_silence_chapters_for_count(&audio, 3, 10.0).unwrap();
+    assert_eq!(chapters.len(), 3);
+    assert!((chapters[1].start_time - 21.0).abs() < 1.0);
+    assert!((chapters[2].start_time - 43.0).abs() < 1.0);
+    assert_eq!(chapters[2].title, "Track 3");
+
+    assert!(detect_silence_chapters_for_count(&audio, 12, 10.0).is_err());
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,178 @@
+//! Tests for SponsorBlock segment handling.
+//!
+//! The API tests talk to a one-shot server on localhost (no network); the
+//! audio-cutting test needs a local `ffmpeg` and is skipped without it.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::Command;
+use youtube_chapter_splitter::audio;
+use youtube_chapter_splitter::chapters::Chapter;
+use youtube_chapter_splitter::sponsorblock::*;
+
+mod common;
+use common::ffmpeg_available;
+
+fn chapters() -> Vec<Chapter> {
+    vec![
+        Chapter::new("First".to_string(), 0.0, 100.0),
+        Chapter::new("Second".to_string(), 100.0, 200.0),
+        Chapter::new("Third".to_string(), 200.0, 300.0),
+    ]
+}
+
+fn segment(start: f64, end: f64, category: &str) -> Segment {
+    Segment {
+        start,
+        end,
+        category: category.to_string(),
+    }
+}
+
+/// Serves one HTTP response on a local port and returns the server's base URL
+/// and a handle yielding the request line it received.
+fn serve_once(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request_line = String::new();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        reader.read_line(&mut request_line).unwrap();
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        stream.write_all(response.as_bytes()).unwrap();
+        request_line
+    });
+    (base, handle)
+}
+
+#[test]
+fn test_parse_segments_keeps_skip_segments_merged_and_sorted() {
+    let json = r#"[
+        {"segment": [150.0, 170.0], "category": "selfpromo", "actionType": "skip"},
+        {"segment": [10.0, 20.0], "category": "sponsor", "actionType": "skip"},
+        {"segment": [15.0, 25.0], "category": "interaction"},
+        {"segment": [50.0, 50.0], "category": "poi_highlight", "actionType": "poi"}
+    ]"#;
+    assert_eq!(
+        parse_segments(json).unwrap(),
+        vec![
+            segment(10.0, 25.0, "sponsor"),
+            segment(150.0, 170.0, "selfpromo")
+        ]
+    );
+    assert!(parse_segments("not json").is_err());
+}
+
+#[test]
+fn test_remove_segments_shifts_chapter_times() {
+    let segments = vec![
+        segment(90.0, 110.0, "sponsor"),
+        segment(200.0, 300.0, "music_offtopic"),
+    ];
+    let result = remove_segments(&chapters(), &segments);
+    let spans: Vec<(&str, f64, f64)> = result
+        .iter()
+        .map(|c| (c.title.as_str(), c.start_time, c.end_time))
+        .collect();
+    // The sponsor read straddling the first cut shortens both chapters; the
+    // third chapter is nothing but a removed segment.
+    assert_eq!(spans, vec![("First", 0.0, 90.0), ("Second", 90.0, 180.0)]);
+}
+
+#[test]
+fn test_mark_segments_makes_segments_chapters() {
+    let segments = vec![
+        segment(30.0, 60.0, "sponsor"),
+        segment(199.5, 230.0, "selfpromo"),
+    ];
+    let titles: Vec<(String, f64, f64)> = mark_segments(&chapters(), &segments)
+        .into_iter()
+        .map(|c| (c.title, c.start_time, c.end_time))
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            ("First".to_string(), 0.0, 30.0),
+            ("[Sponsor]".to_string(), 30.0, 60.0),
+            ("First".to_string(), 60.0, 100.0),
+            ("Second".to_string(), 100.0, 200.0),
+            ("[Self-promotion]".to_string(), 200.0, 230.0),
+            ("Third".to_string(), 230.0, 300.0),
+        ]
+    );
+}
+
+#[test]
+fn test_fetch_segments_from_local_server() {
+    let (base, server) = serve_once(
+        "200 OK",
+        r#"[{"segment": [5.0, 12.5], "category": "sponsor", "actionType": "skip"}]"#,
+    );
+    let segments =
+        fetch_segments(&format!("{}/", base), "dQw4w9WgXcQ", &default_categories()).unwrap();
+    assert_eq!(segments, vec![segment(5.0, 12.5, "sponsor")]);
+    let request = server.join().unwrap();
+    assert!(request.starts_with("GET /api/skipSegments?videoID=dQw4w9WgXcQ&categories="));
+}
+
+#[test]
+fn test_fetch_segments_not_found_is_empty() {
+    let (base, server) = serve_once("404 Not Found", "Not Found");
+    assert!(
+        fetch_segments(&base, "dQw4w9WgXcQ", &default_categories())
+            .unwrap()
+            .is_empty()
+    );
+    server.join().unwrap();
+
+    let (base, server) = serve_once("500 Internal Server Error", "oops");
+    assert!(fetch_segments(&base, "dQw4w9WgXcQ", &default_categories()).is_err());
+    server.join().unwrap();
+}
+
+#[test]
+fn test_remove_time_ranges_shortens_audio() {
+    if !ffmpeg_available() {
+        eprintln!("Skipping: ffmpeg not available");
+        return;
+    }
+    let root = std::env::temp_dir().join("ytcs_sponsorblock_cut");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    let source = root.join("temp_audio.mp3");
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            "anullsrc=r=8000:cl=mono",
+            "-t",
+            "10",
+            "-c:a",
+            "libmp3lame",
+            source.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success());
+
+    let cut = root.join("temp_audio_sponsorblock.flac");
+    audio::remove_time_ranges(&source, &[(2.0, 4.0), (6.0, 7.0)], &cut).unwrap();
+    let duration = audio::get_audio_duration(&cut).unwrap();
+    assert!((duration - 7.0).abs() < 0.2, "got {}s", duration);
+    assert!(source.exists(), "the download must be left untouched");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
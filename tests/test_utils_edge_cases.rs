@@ -3,8 +3,8 @@
 #[cfg(test)]
 mod utils_edge_cases_tests {
     use youtube_chapter_splitter::utils::{
-        clean_folder_name, format_duration, format_duration_short, parse_artist_album,
-        sanitize_title,
+        artist_from_channel, clean_folder_name, extract_release_year, format_duration,
+        format_duration_short, parse_artist_album, sanitize_title,
     };
 
     // Tests pour clean_folder_name
@@ -186,4 +186,71 @@ mod utils_edge_cases_tests {
         // 3661 secondes = 1h 1m 1s, mais format_short n'affiche que minutes
         assert_eq!(format_duration_short(3661.0), "61m 01s");
     }
+
+    // Tests pour artist_from_channel
+
+    #[test]
+    fn test_artist_from_channel_strips_decorations() {
+        assert_eq!(
+            artist_from_channel("Marigold - Topic").as_deref(),
+            Some("Marigold")
+        );
+        assert_eq!(
+            artist_from_channel("PinkFloydVEVO").as_deref(),
+            Some("PinkFloyd")
+        );
+        assert_eq!(
+            artist_from_channel("Purple Dreams Official Channel").as_deref(),
+            Some("Purple Dreams")
+        );
+        assert_eq!(
+            artist_from_channel("Wandering Shadows [OFFICIAL]").as_deref(),
+            Some("Wandering Shadows")
+        );
+        assert_eq!(
+            artist_from_channel("Officially Loud").as_deref(),
+            Some("Officially Loud")
+        );
+    }
+
+    #[test]
+    fn test_artist_from_channel_empty_result() {
+        assert_eq!(artist_from_channel("   "), None);
+        assert_eq!(artist_from_channel("Official"), None);
+    }
+
+    // Tests pour extract_release_year
+
+    #[test]
+    fn test_extract_release_year_from_title_brackets() {
+        assert_eq!(
+            extract_release_year("Pink Floyd - Dark Side (1973) [Full Album]", None),
+            Some(1973)
+        );
+        // A remaster year next to other words is not the release year.
+        assert_eq!(
+            extract_release_year("Pink Floyd - Dark Side (Remastered 2011)", None),
+            None
+        );
+        assert_eq!(extract_release_year("Band - Album [1850]", None), None);
+    }
+
+    #[test]
+    fn test_extract_release_year_from_description() {
+        let desc = "Tracklist:\n00:00 Intro\n\nRelease date: March 1, 1973\n(c) 2011 Label";
+        assert_eq!(extract_release_year("Band - Album", Some(desc)), Some(1973));
+        assert_eq!(
+            extract_release_year("Band - Album", Some("℗ 1998 Some Records")),
+            Some(1998)
+        );
+        // The title wins over the description.
+        assert_eq!(
+            extract_release_year("Band - Album [1980]", Some(desc)),
+            Some(1980)
+        );
+        assert_eq!(
+            extract_release_year("Band - Album", Some("Recorded in 1999 at home")),
+            None
+        );
+    }
 }